@@ -1,4 +1,5 @@
 use core::{
+	iter::Sum,
 	ops::{
 		Add,
 		AddAssign,
@@ -182,3 +183,41 @@ impl<T: Into<u128>> RemAssign<T> for Duration {
 		self.0 %= rhs.into();
 	}
 }
+
+// Summation
+
+impl Sum<Duration> for Duration {
+	/// #### Panics
+	/// Panics if the sum overflows; see [Duration::checked_sum] for a
+	/// non-panicking version.
+	fn sum<I: Iterator<Item = Duration>>(iter: I) -> Self {
+		Self::checked_sum(iter).expect("overflow when summing durations")
+	}
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+	/// #### Panics
+	/// Panics if the sum overflows; see [Duration::checked_sum] for a
+	/// non-panicking version.
+	fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Self {
+		Self::checked_sum(iter.copied()).expect("overflow when summing durations")
+	}
+}
+
+impl Sum<StdDuration> for Duration {
+	/// #### Panics
+	/// Panics if the sum overflows; see [Duration::checked_sum] for a
+	/// non-panicking version.
+	fn sum<I: Iterator<Item = StdDuration>>(iter: I) -> Self {
+		Self::checked_sum(iter).expect("overflow when summing durations")
+	}
+}
+
+impl<'a> Sum<&'a StdDuration> for Duration {
+	/// #### Panics
+	/// Panics if the sum overflows; see [Duration::checked_sum] for a
+	/// non-panicking version.
+	fn sum<I: Iterator<Item = &'a StdDuration>>(iter: I) -> Self {
+		Self::checked_sum(iter.copied()).expect("overflow when summing durations")
+	}
+}
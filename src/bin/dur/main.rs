@@ -0,0 +1,325 @@
+use std::{
+	fmt::Write as _,
+	io::{
+		self,
+		BufRead,
+	},
+	process::ExitCode,
+};
+
+use clap::{
+	Parser,
+	Subcommand,
+	ValueEnum,
+};
+use dur::{
+	Decimal,
+	Duration,
+	Unit,
+};
+use serde::Serialize;
+
+const DAY: u128 = Duration::DAY.as_nanos();
+
+/// Parse, convert and do arithmetic on human-readable durations.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+	/// Print the output as JSON.
+	#[arg(long, global = true)]
+	json: bool,
+
+	#[command(subcommand)]
+	cmd: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+	/// Parse a duration and print it back in the given style.
+	Fmt {
+		#[command(flatten)]
+		style: StyleArgs,
+		/// The duration; multiple arguments are joined with spaces.
+		#[arg(required = true)]
+		duration: Vec<String>,
+	},
+	/// Convert a duration into a single unit.
+	To {
+		/// The unit to convert to, e.g. `h` or `seconds`.
+		unit: Unit,
+		/// The duration; multiple arguments are joined with spaces.
+		#[arg(required = true)]
+		duration: Vec<String>,
+	},
+	/// Add up the durations read from stdin, one per line.
+	Sum {
+		#[command(flatten)]
+		style: StyleArgs,
+	},
+	/// Evaluate an arithmetic expression such as "1h30m * 3 - 20m".
+	Calc {
+		#[command(flatten)]
+		style: StyleArgs,
+		/// The expression; multiple arguments are joined with spaces.
+		#[arg(required = true)]
+		expr: Vec<String>,
+	},
+}
+
+#[derive(clap::Args)]
+struct StyleArgs {
+	/// The output style.
+	#[arg(short, long, value_enum, default_value_t = Style::Pretty)]
+	style: Style,
+	/// The maximum number of digits after the decimal point (pretty and long
+	/// styles only).
+	#[arg(short, long)]
+	precision: Option<usize>,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Style {
+	/// Rounded, short units: `1h 30m`.
+	Pretty,
+	/// Lossless, short units: `1h 30m 0.5s`.
+	Exact,
+	/// Rounded, full units: `1 hour 30 minutes`.
+	Long,
+	/// ISO 8601: `PT1H30M`.
+	Iso,
+}
+
+#[derive(Serialize)]
+struct Report {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	count: Option<usize>,
+	nanos: u128,
+	pretty: String,
+	exact: String,
+	long: String,
+	iso: String,
+}
+
+#[derive(Serialize)]
+struct Conversion {
+	unit: &'static str,
+	value: String,
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+	error: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	line: Option<usize>,
+	input: &'a str,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	position: Option<usize>,
+}
+
+struct Failure {
+	msg: String,
+	input: String,
+	line: Option<usize>,
+	/// The byte offset into `input`, if known.
+	pos: Option<usize>,
+}
+
+impl Failure {
+	fn new(msg: impl ToString, input: &str) -> Self {
+		Self {
+			msg: msg.to_string(),
+			input: input.to_string(),
+			line: None,
+			pos: None,
+		}
+	}
+
+	fn parse(e: dur::Error, input: &str) -> Self {
+		Self {
+			pos: Some(e.span().start),
+			..Self::new(&e, input)
+		}
+	}
+
+	fn print(&self, json: bool) {
+		let position = self.pos.map(|pos| self.input[..pos].chars().count());
+		if json {
+			let report = ErrorReport {
+				error: self.msg.clone(),
+				line: self.line,
+				input: &self.input,
+				position,
+			};
+			eprintln!("{}", serde_json::to_string(&report).unwrap());
+			return;
+		}
+
+		match self.line {
+			Some(line) => eprintln!("error: line {line}: {}", self.msg),
+			None => eprintln!("error: {}", self.msg),
+		}
+		eprintln!("  {}", self.input);
+		if let Some(position) = position {
+			eprintln!("  {:>width$}", "^", width = position + 1);
+		}
+	}
+}
+
+fn iso(d: Duration) -> String {
+	let n = d.as_nanos();
+	if n == 0 {
+		return "PT0S".into();
+	}
+
+	let days = n / DAY;
+	let hours = n % DAY / Duration::HOUR.as_nanos();
+	let mins = n % Duration::HOUR.as_nanos() / Duration::MINUTE.as_nanos();
+	let secs = n % Duration::MINUTE.as_nanos() / Duration::SECOND.as_nanos();
+	let frac = n % Duration::SECOND.as_nanos();
+
+	let mut s = String::from("P");
+	if days != 0 {
+		write!(s, "{days}D").unwrap();
+	}
+	if !n.is_multiple_of(DAY) {
+		s.push('T');
+	}
+	if hours != 0 {
+		write!(s, "{hours}H").unwrap();
+	}
+	if mins != 0 {
+		write!(s, "{mins}M").unwrap();
+	}
+	if frac != 0 {
+		let frac = format!("{frac:09}");
+		write!(s, "{secs}.{}S", frac.trim_end_matches('0')).unwrap();
+	} else if secs != 0 {
+		write!(s, "{secs}S").unwrap();
+	}
+
+	s
+}
+
+fn report(d: Duration, count: Option<usize>, style: &StyleArgs, json: bool) {
+	let precision = style.precision.unwrap_or(2);
+	if json {
+		let r = Report {
+			count,
+			nanos: d.as_nanos(),
+			pretty: format!("{d:.precision$}"),
+			exact: d.format_exact().to_string(),
+			long: format!("{d:#.precision$}"),
+			iso: iso(d),
+		};
+		println!("{}", serde_json::to_string(&r).unwrap());
+		return;
+	}
+
+	match style.style {
+		Style::Pretty => println!("{d:.precision$}"),
+		Style::Exact => println!("{}", d.format_exact()),
+		Style::Long => println!("{d:#.precision$}"),
+		Style::Iso => println!("{}", iso(d)),
+	}
+}
+
+fn convert(unit: Unit, input: &str, json: bool) -> Result<(), Failure> {
+	let d = dur::parse(input).map_err(|e| Failure::parse(e, input))?;
+
+	if d.in_whole(unit) > Decimal::MAX.mantissa() as u128 {
+		return Err(Failure::new(
+			"the value is too big to display in this unit",
+			input,
+		));
+	}
+	let value = d.as_unit(unit).normalize().to_string();
+
+	if json {
+		let c = Conversion {
+			unit: unit.long_name(),
+			value,
+		};
+		println!("{}", serde_json::to_string(&c).unwrap());
+	} else {
+		println!("{value}");
+	}
+
+	Ok(())
+}
+
+fn sum(style: &StyleArgs, json: bool) -> Result<(), Failure> {
+	let mut total = Duration::from_nanos(0);
+	let mut count = 0;
+
+	for (i, line) in io::stdin().lock().lines().enumerate() {
+		let line = line.map_err(|e| Failure::new(e, ""))?;
+		let s = line.trim();
+		if s.is_empty() {
+			continue;
+		}
+
+		let with_line = |mut f: Failure| {
+			f.line = Some(i + 1);
+			f
+		};
+
+		let d = dur::parse(s).map_err(|e| with_line(Failure::parse(e, s)))?;
+		total = total
+			.as_nanos()
+			.checked_add(d.as_nanos())
+			.map(Duration::from_nanos)
+			.ok_or_else(|| with_line(Failure::new("the sum is too big to store", s)))?;
+		count += 1;
+	}
+
+	report(total, Some(count), style, json);
+	Ok(())
+}
+
+fn run(args: &Args) -> Result<(), Failure> {
+	match &args.cmd {
+		Cmd::Fmt { style, duration } => {
+			let input = duration.join(" ");
+			let d = dur::parse(&input).map_err(|e| Failure::parse(e, &input))?;
+			report(d, None, style, args.json);
+		}
+		Cmd::To { unit, duration } => convert(*unit, &duration.join(" "), args.json)?,
+		Cmd::Sum { style } => sum(style, args.json)?,
+		Cmd::Calc { style, expr } => {
+			let input = expr.join(" ");
+			let d = dur::eval(&input).map_err(|e| Failure::parse(e, &input))?;
+			report(d, None, style, args.json);
+		}
+	}
+
+	Ok(())
+}
+
+fn main() -> ExitCode {
+	let args = Args::parse();
+	match run(&args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(e) => {
+			e.print(args.json);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn iso() {
+		let tests = [
+			("0s", "PT0S"),
+			("1d", "P1D"),
+			("1h 30m", "PT1H30M"),
+			("2d 3h 4m 5.25s", "P2DT3H4M5.25S"),
+			("1d 1ns", "P1DT0.000000001S"),
+		];
+
+		for (s, expected) in tests {
+			assert_eq!(expected, super::iso(dur::parse(s).unwrap()));
+		}
+	}
+}
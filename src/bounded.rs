@@ -0,0 +1,127 @@
+use crate::*;
+
+/// A [Duration] between `MIN_NS` and `MAX_NS` nanoseconds, inclusive.
+///
+/// Out of range values are rejected when parsing and, with the `serde` and
+/// `clap` features, when deserializing and parsing command line arguments, so
+/// a configuration field of this type is always valid.
+/// The bounds are nanoseconds because const generics can't be [Duration]s, but
+/// the const constructors of [Duration] can write them.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{
+///     BoundedDuration,
+///     Duration,
+///     ErrorKind,
+/// };
+///
+/// type Interval = BoundedDuration<
+///     { Duration::from_secs(1).as_nanos() },
+///     { Duration::from_hours(1).as_nanos() },
+/// >;
+///
+/// let i = "5m".parse::<Interval>().unwrap();
+/// assert_eq!(i.get(), Duration::from_mins(5));
+///
+/// let e = "2h".parse::<Interval>().unwrap_err();
+/// assert_eq!(
+///     e.kind(),
+///     ErrorKind::OutOfRange {
+///         min: Duration::from_secs(1),
+///         max: Duration::from_hours(1),
+///     }
+/// );
+///
+/// assert_eq!(Interval::new_saturating(Duration::ZERO), Interval::MIN);
+/// assert_eq!(Interval::new(Duration::from_hours(2)), None);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BoundedDuration<const MIN_NS: u128, const MAX_NS: u128>(Duration);
+
+impl<const MIN_NS: u128, const MAX_NS: u128> BoundedDuration<MIN_NS, MAX_NS> {
+	/// Fails to compile if the bounds are the wrong way around.
+	const BOUNDS: () = assert!(MIN_NS <= MAX_NS, "MIN_NS is greater than MAX_NS");
+
+	/// The smallest value.
+	pub const MIN: Self = Self::new_saturating(Duration(MIN_NS));
+	/// The largest value.
+	pub const MAX: Self = Self::new_saturating(Duration(MAX_NS));
+
+	/// Returns `d` if it's in range, otherwise [None].
+	pub const fn new(d: Duration) -> Option<Self> {
+		let () = Self::BOUNDS;
+		if d.0 < MIN_NS || d.0 > MAX_NS {
+			None
+		} else {
+			Some(Self(d))
+		}
+	}
+
+	/// Returns `d` clamped to the range.
+	pub const fn new_saturating(d: Duration) -> Self {
+		let () = Self::BOUNDS;
+		if d.0 < MIN_NS {
+			Self(Duration(MIN_NS))
+		} else if d.0 > MAX_NS {
+			Self(Duration(MAX_NS))
+		} else {
+			Self(d)
+		}
+	}
+
+	/// Returns the duration.
+	pub const fn get(self) -> Duration {
+		self.0
+	}
+
+	/// Parses a duration with [parse] and checks that it's in range.
+	///
+	/// #### Errors
+	/// Returns an error if [parse] does or, if the duration is out of range,
+	/// an [ErrorKind::OutOfRange] spanning all of `input`.
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		Self::try_from(parse(input)?)
+			.map_err(|e| Error::new(e.kind(), 0..input.len()).with_input(input))
+	}
+}
+
+impl<const MIN_NS: u128, const MAX_NS: u128> FromStr for BoundedDuration<MIN_NS, MAX_NS> {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse(s)
+	}
+}
+
+impl<const MIN_NS: u128, const MAX_NS: u128> Display for BoundedDuration<MIN_NS, MAX_NS> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+/// Fails with an [ErrorKind::OutOfRange] if the duration is out of range.
+impl<const MIN_NS: u128, const MAX_NS: u128> TryFrom<Duration> for BoundedDuration<MIN_NS, MAX_NS> {
+	type Error = Error;
+
+	fn try_from(d: Duration) -> Result<Self, Self::Error> {
+		Self::new(d).ok_or(Error::from(ErrorKind::OutOfRange {
+			min: Self::MIN.0,
+			max: Self::MAX.0,
+		}))
+	}
+}
+
+impl<const MIN_NS: u128, const MAX_NS: u128> From<BoundedDuration<MIN_NS, MAX_NS>> for Duration {
+	fn from(d: BoundedDuration<MIN_NS, MAX_NS>) -> Self {
+		d.0
+	}
+}
+
+impl<const MIN_NS: u128, const MAX_NS: u128> PartialEq<Duration>
+	for BoundedDuration<MIN_NS, MAX_NS>
+{
+	fn eq(&self, rhs: &Duration) -> bool {
+		self.0 == *rhs
+	}
+}
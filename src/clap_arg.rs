@@ -1,4 +1,9 @@
-use std::ffi::OsStr;
+use std::{
+	error::Error as _,
+	ffi::OsStr,
+	fmt,
+	marker::PhantomData,
+};
 
 use clap::{
 	builder::{
@@ -9,26 +14,84 @@ use clap::{
 	error::{
 		ContextKind,
 		ContextValue,
-		ErrorKind,
 	},
 	Arg,
 	Command,
 };
 
-use crate::Duration;
+use crate::{
+	dialect::{
+		self,
+		Dialect,
+	},
+	granularity::Granularity,
+	BoundedDuration,
+	Duration,
+	Timeout,
+	Whole,
+};
+
+/// A clap value parser that parses a [Duration] in a [Dialect], for use with
+/// `#[arg(value_parser = dur::dialect::ClapParser::<D>::new())]`.
+///
+/// [Duration] itself uses `ClapParser<dialect::Default>`.
+///
+/// #### Examples
+/// ```rust
+/// use clap::{
+///     Arg,
+///     Command,
+/// };
+/// use dur::{
+///     dialect::{
+///         ClapParser,
+///         Go,
+///     },
+///     Duration,
+/// };
+///
+/// let cmd = Command::new("sleep").arg(Arg::new("time").value_parser(ClapParser::<Go>::new()));
+/// let m = cmd.get_matches_from(["sleep", "1h30m"]);
+/// assert_eq!(m.get_one::<Duration>("time"), Some(&Duration::from_mins(90)));
+/// ```
+pub struct ClapParser<D>(PhantomData<fn() -> D>);
+
+impl<D> ClapParser<D> {
+	/// Creates a new parser.
+	pub const fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<D> Default for ClapParser<D> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<D> Clone for ClapParser<D> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
 
-#[derive(Clone, Debug)]
-pub struct DurationParser;
+impl<D> Copy for ClapParser<D> {}
+
+impl<D> fmt::Debug for ClapParser<D> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("ClapParser")
+	}
+}
 
 impl ValueParserFactory for Duration {
-	type Parser = DurationParser;
+	type Parser = ClapParser<dialect::Default>;
 
 	fn value_parser() -> Self::Parser {
-		DurationParser
+		ClapParser::new()
 	}
 }
 
-impl TypedValueParser for DurationParser {
+impl<D: Dialect + 'static> TypedValueParser for ClapParser<D> {
 	type Value = Duration;
 
 	fn parse_ref(
@@ -37,18 +100,118 @@ impl TypedValueParser for DurationParser {
 		arg: Option<&Arg>,
 		value: &OsStr,
 	) -> Result<Self::Value, clap::Error> {
-		let s = StringValueParser::new().parse_ref(cmd, arg, value)?;
-		crate::parse(&s).map_err(move |e| {
-			let mut err = clap::Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
-			if let Some(arg) = arg {
-				err.insert(
-					ContextKind::InvalidArg,
-					ContextValue::String(arg.to_string()),
-				);
+		parse_ref(cmd, arg, value, D::parse)
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct TimeoutParser;
+
+impl ValueParserFactory for Timeout {
+	type Parser = TimeoutParser;
+
+	fn value_parser() -> Self::Parser {
+		TimeoutParser
+	}
+}
+
+impl TypedValueParser for TimeoutParser {
+	type Value = Timeout;
+
+	fn parse_ref(
+		&self,
+		cmd: &Command,
+		arg: Option<&Arg>,
+		value: &OsStr,
+	) -> Result<Self::Value, clap::Error> {
+		parse_ref(cmd, arg, value, Timeout::parse)
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct BoundedParser<const MIN_NS: u128, const MAX_NS: u128>;
+
+impl<const MIN_NS: u128, const MAX_NS: u128> ValueParserFactory
+	for BoundedDuration<MIN_NS, MAX_NS>
+{
+	type Parser = BoundedParser<MIN_NS, MAX_NS>;
+
+	fn value_parser() -> Self::Parser {
+		BoundedParser
+	}
+}
+
+impl<const MIN_NS: u128, const MAX_NS: u128> TypedValueParser for BoundedParser<MIN_NS, MAX_NS> {
+	type Value = BoundedDuration<MIN_NS, MAX_NS>;
+
+	fn parse_ref(
+		&self,
+		cmd: &Command,
+		arg: Option<&Arg>,
+		value: &OsStr,
+	) -> Result<Self::Value, clap::Error> {
+		parse_ref(cmd, arg, value, BoundedDuration::parse)
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WholeParser<G>(PhantomData<G>);
+
+impl<G: Granularity> ValueParserFactory for Whole<G> {
+	type Parser = WholeParser<G>;
+
+	fn value_parser() -> Self::Parser {
+		WholeParser(PhantomData)
+	}
+}
+
+impl<G: Granularity> TypedValueParser for WholeParser<G> {
+	type Value = Whole<G>;
+
+	fn parse_ref(
+		&self,
+		cmd: &Command,
+		arg: Option<&Arg>,
+		value: &OsStr,
+	) -> Result<Self::Value, clap::Error> {
+		parse_ref(cmd, arg, value, Whole::parse)
+	}
+}
+
+fn parse_ref<T>(
+	cmd: &Command,
+	arg: Option<&Arg>,
+	value: &OsStr,
+	parse: fn(&str) -> Result<T, crate::Error>,
+) -> Result<T, clap::Error>
+where
+	T: Clone + Send + Sync + 'static,
+{
+	// `try_map` keeps the parse error as the source, which clap prints.
+	StringValueParser::new()
+		.try_map(move |s| parse(&s))
+		.parse_ref(cmd, arg, value)
+		.map_err(|mut err| {
+			let suggested = match (
+				err.get(ContextKind::InvalidValue),
+				err.source().and_then(|e| e.downcast_ref::<crate::Error>()),
+			) {
+				(Some(ContextValue::String(input)), Some(e)) => suggested_input(input, e),
+				_ => None,
+			};
+			if let Some(s) = suggested {
+				err.insert(ContextKind::SuggestedValue, ContextValue::String(s));
 			}
-			err.insert(ContextKind::InvalidValue, ContextValue::String(s));
-			err.insert(ContextKind::Custom, ContextValue::String(e.to_string()));
 			err
 		})
-	}
+}
+
+/// Returns `input` with the unrecognized unit replaced with the suggested one.
+fn suggested_input(input: &str, e: &crate::Error) -> Option<String> {
+	let suggestion = e.suggestion()?;
+	let span = e.span();
+	input.get(span.clone())?;
+	let mut s = input.to_owned();
+	s.replace_range(span, suggestion);
+	Some(s)
 }
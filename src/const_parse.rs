@@ -0,0 +1,404 @@
+use crate::*;
+
+/// The largest mantissa a [Decimal] can hold, `2^96 - 1`.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+/// The largest scale a [Decimal] can hold.
+const MAX_SCALE: u32 = 28;
+/// [Decimal::MAX] as an integer.
+const DECIMAL_MAX: u128 = MAX_MANTISSA;
+
+/// The error kinds of [parse_const].
+///
+/// [Error] has a destructor with the `alloc` feature, which can't run in a
+/// `const fn`, so it's only created at the very end.
+#[derive(Copy, Clone)]
+enum Fail {
+	InvalidDuration,
+	ValueTooBig,
+	MissingUnit,
+	IsNegative(Decimal),
+	InvalidUnit,
+}
+
+impl Fail {
+	const fn kind(self) -> ErrorKind {
+		match self {
+			Self::InvalidDuration => ErrorKind::InvalidDuration,
+			Self::ValueTooBig => ErrorKind::ValueTooBig,
+			Self::MissingUnit => ErrorKind::MissingUnit,
+			Self::IsNegative(d) => ErrorKind::IsNegative(d),
+			Self::InvalidUnit => ErrorKind::InvalidUnit,
+		}
+	}
+
+	const fn message(self) -> &'static str {
+		match self {
+			Self::InvalidDuration => "invalid duration literal",
+			Self::ValueTooBig => "the duration literal is too big to store",
+			Self::MissingUnit => "missing unit after number in duration literal",
+			Self::IsNegative(_) => "duration literals cannot be negative",
+			Self::InvalidUnit => "invalid unit in duration literal",
+		}
+	}
+}
+
+/// A number as written in the input: `mantissa / 10^scale`.
+#[derive(Copy, Clone)]
+struct Num {
+	mantissa: u128,
+	scale: u32,
+	/// Whether the sign is negative; like with [Decimal], only an exponent
+	/// can round a negative number to a negative zero.
+	negative: bool,
+}
+
+impl Num {
+	const fn is_negative(self) -> bool {
+		self.negative
+	}
+
+	const fn to_decimal(self) -> Decimal {
+		Decimal::from_parts(
+			self.mantissa as u32,
+			(self.mantissa >> 32) as u32,
+			(self.mantissa >> 64) as u32,
+			self.negative,
+			self.scale,
+		)
+	}
+
+	/// Multiplies the number by `unit`, truncating fractions of a nanosecond.
+	const fn times(self, unit: u128) -> Result<u128, Fail> {
+		let pow = 10_u128.pow(self.scale);
+		let whole = match (self.mantissa / pow).checked_mul(unit) {
+			Some(n) => n,
+			None => return Err(Fail::ValueTooBig),
+		};
+
+		let (mut frac, mut pow) = (self.mantissa % pow, pow);
+		// Drop the least significant digits until the product fits; they're
+		// far below a nanosecond.
+		let frac = loop {
+			match frac.checked_mul(unit) {
+				Some(n) => break n / pow,
+				None => {
+					frac /= 10;
+					pow /= 10;
+				}
+			}
+		};
+
+		match whole.checked_add(frac) {
+			// `parse` multiplies with `Decimal`, which has a smaller range.
+			Some(n) if n <= DECIMAL_MAX => Ok(n),
+			_ => Err(Fail::ValueTooBig),
+		}
+	}
+}
+
+const fn is_space(b: u8) -> bool {
+	b == b' ' || b == b'\t'
+}
+
+/// Non-ASCII bytes are treated as alphabetic since they can't be decoded in a
+/// `const fn`; either way they're not a valid continuation.
+const fn is_alphabetic(b: u8) -> bool {
+	b.is_ascii_alphabetic() || !b.is_ascii()
+}
+
+/// A [Fail] and the byte range of the input that caused it.
+type Spanned = (Fail, usize, usize);
+
+/// Parses `[+-]digits[.digits]` starting at `i`, in the same shape as
+/// `Decimal`'s `FromStr` implementation.
+///
+/// Returns the number and the index after it, or [None] if there are no
+/// digits at `i`.
+/// If `underscores` is true, `_` is accepted after the first digit.
+const fn number(
+	s: &[u8],
+	mut i: usize,
+	underscores: bool,
+) -> Result<Option<(Num, usize)>, Spanned> {
+	let start = i;
+	let mut n = Num {
+		mantissa: 0,
+		scale: 0,
+		negative: false,
+	};
+
+	if i < s.len() && (s[i] == b'-' || s[i] == b'+') {
+		n.negative = s[i] == b'-';
+		i += 1;
+	}
+
+	let mut digits = 0;
+	let mut point = false;
+	// Fractional digits that don't fit are rounded like `Decimal` does.
+	let mut truncated = false;
+	let mut overflow = false;
+	while i < s.len() {
+		let b = s[i];
+		if b == b'.' && !point {
+			point = true;
+		} else if b == b'_' && underscores && digits != 0 {
+		} else if b.is_ascii_digit() {
+			let d = (b - b'0') as u128;
+			digits += 1;
+			if truncated || overflow {
+				// Skip the remaining digits.
+			} else if point && (n.scale == MAX_SCALE || n.mantissa * 10 + d > MAX_MANTISSA) {
+				truncated = true;
+				if d >= 5 && n.mantissa < MAX_MANTISSA {
+					n.mantissa += 1;
+				}
+			} else if n.mantissa * 10 + d > MAX_MANTISSA {
+				// Keep going to find the end of the number.
+				overflow = true;
+			} else {
+				n.mantissa = n.mantissa * 10 + d;
+				if point {
+					n.scale += 1;
+				}
+			}
+		} else {
+			break;
+		}
+		i += 1;
+	}
+
+	if overflow {
+		Err((Fail::InvalidDuration, start, i))
+	} else if digits == 0 {
+		Ok(None)
+	} else {
+		// `-0` isn't negative.
+		n.negative = n.negative && n.mantissa != 0;
+		Ok(Some((n, i)))
+	}
+}
+
+/// Applies the exponent that makes up the rest of `s` from `i`, such as `e3`
+/// or `E-2`, to `n` the way `Decimal`'s `FromStr` implementation does.
+///
+/// Returns [None] if the rest of `s` isn't an exponent or the result doesn't
+/// fit in a [Decimal].
+const fn exponent(mut n: Num, s: &[u8], mut i: usize) -> Option<Num> {
+	if i == s.len() || !(s[i] == b'e' || s[i] == b'E') {
+		return None;
+	}
+	i += 1;
+	let negative = i < s.len() && s[i] == b'-';
+	if negative {
+		i += 1;
+	}
+	// The exponent is parsed as a `u32`, which allows a `+`.
+	if i < s.len() && s[i] == b'+' {
+		i += 1;
+	}
+	if i == s.len() {
+		return None;
+	}
+	let mut exp = 0_u32;
+	while i < s.len() {
+		if !s[i].is_ascii_digit() {
+			return None;
+		}
+		exp = match exp.checked_mul(10) {
+			Some(e) => match e.checked_add((s[i] - b'0') as u32) {
+				Some(e) => e,
+				None => return None,
+			},
+			None => return None,
+		};
+		i += 1;
+	}
+
+	if negative {
+		if exp > MAX_SCALE {
+			return None;
+		}
+		if n.scale + exp > MAX_SCALE {
+			// Round to the largest scale, on the most significant dropped
+			// digit only.
+			let pow = 10_u128.pow(n.scale + exp - MAX_SCALE - 1);
+			let dropped = n.mantissa / pow;
+			n.mantissa = dropped / 10 + (dropped % 10 >= 5) as u128;
+			n.scale = MAX_SCALE;
+		} else {
+			n.scale += exp;
+		}
+	} else if exp <= n.scale {
+		n.scale -= exp;
+	} else if exp > MAX_SCALE {
+		return None;
+	} else {
+		n.mantissa = match n.mantissa.checked_mul(10_u128.pow(exp - n.scale)) {
+			Some(m) if m <= MAX_MANTISSA => m,
+			_ => return None,
+		};
+		n.scale = 0;
+	}
+	Some(n)
+}
+
+/// Parses a duration in a `const` context.
+///
+/// This accepts the same syntax as [parse] and produces the same values,
+/// with a few differences in error reporting:
+/// - The error doesn't hold the input or a [suggestion](Error::suggestion).
+/// - Non-ASCII characters are treated as letters.
+///
+/// Prefer the [dur!](crate::dur!) macro for declaring constants, which
+/// reports malformed durations at compile time.
+/// With the `alloc` feature, [Error] can't be dropped in a `const` context,
+/// which makes the macro the only convenient way to use this in `const`
+/// items.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(dur::parse_const("1m 30.5s"), Ok(Duration::from_millis(90_500)));
+/// ```
+pub const fn parse_const(input: &str) -> Result<Duration, Error> {
+	match parse_impl(input) {
+		Ok(d) => Ok(d),
+		Err((fail, start, end)) => Err(Error::new(fail.kind(), start..end)),
+	}
+}
+
+/// Returns the index of the first ASCII whitespace at or after `i`.
+const fn token_end(s: &[u8], mut i: usize) -> usize {
+	while i < s.len() && !s[i].is_ascii_whitespace() {
+		i += 1;
+	}
+	i
+}
+
+const fn parse_impl(input: &str) -> Result<Duration, Spanned> {
+	let s = input.as_bytes();
+
+	if s.is_empty() || input.trim_ascii().is_empty() {
+		return Err((Fail::InvalidDuration, 0, s.len()));
+	}
+
+	// A lone number is in milliseconds.
+	let lone = match number(s, 0, true) {
+		Ok(Some((n, end))) if end == s.len() => Some(n),
+		// `Decimal` also accepts an exponent, such as `1e3`.
+		Ok(Some((n, end))) => exponent(n, s, end),
+		// This isn't a lone number, so `parse` would try the full syntax.
+		Ok(None) | Err(_) => None,
+	};
+	if let Some(n) = lone {
+		if n.is_negative() {
+			return Err((Fail::IsNegative(n.to_decimal()), 0, s.len()));
+		}
+		return match n.times(MILLISECOND) {
+			Ok(ns) => Ok(Duration(ns)),
+			Err(e) => Err((e, 0, s.len())),
+		};
+	}
+
+	let mut i = 0;
+	let mut total = 0_u128;
+	let mut first = true;
+
+	while i < s.len() || first {
+		if !first {
+			if s[i] == b',' {
+				i += 1;
+			}
+			while i < s.len() && is_space(s[i]) {
+				i += 1;
+			}
+			if i == s.len() {
+				break;
+			}
+		}
+		first = false;
+
+		let start = i;
+		let (n, end) = match number(s, i, false) {
+			Ok(Some(x)) => x,
+			Ok(None) => return Err((Fail::InvalidDuration, i, token_end(s, i))),
+			Err(e) => return Err(e),
+		};
+		if n.is_negative() {
+			return Err((Fail::IsNegative(n.to_decimal()), start, end));
+		}
+
+		i = end;
+		while i < s.len() && is_space(s[i]) {
+			i += 1;
+		}
+
+		let (_, rest) = s.split_at(i);
+		if rest.trim_ascii().is_empty() {
+			return Err((Fail::MissingUnit, start, end));
+		}
+		let unit = match unit::match_unit(rest) {
+			Some((unit, len)) if !(len < rest.len() && is_alphabetic(rest[len])) => {
+				i += len;
+				unit
+			}
+			_ => {
+				// Point at the word, or the first character if it's not a
+				// letter.
+				let mut len = 0;
+				while len < rest.len() && is_alphabetic(rest[len]) {
+					len += 1;
+				}
+				if len == 0 {
+					len = 1;
+				}
+				return Err((Fail::InvalidUnit, i, i + len));
+			}
+		};
+
+		let ns = match n.times(unit.nanos()) {
+			Ok(ns) => ns,
+			Err(e) => return Err((e, start, i)),
+		};
+		total = match total.checked_add(ns) {
+			Some(n) => n,
+			None => return Err((Fail::ValueTooBig, start, i)),
+		};
+	}
+
+	Ok(Duration(total))
+}
+
+#[doc(hidden)]
+pub const fn __parse_literal(input: &str) -> Duration {
+	match parse_impl(input) {
+		Ok(d) => d,
+		Err((e, ..)) => panic!("{}", e.message()),
+	}
+}
+
+/// Creates a [Duration] from a string literal at compile time.
+///
+/// The string uses the same syntax as [parse] and is parsed with
+/// [parse_const] inside a `const` block, so a malformed duration is a compile
+/// error.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{dur, Duration};
+///
+/// const TIMEOUT: Duration = dur!("1m 30s");
+/// assert_eq!(TIMEOUT, Duration::from_secs(90));
+/// assert_eq!(dur!("1.5h"), Duration::from_mins(90));
+/// ```
+///
+/// ```compile_fail
+/// const TIMEOUT: dur::Duration = dur::dur!("1m 30 parsecs");
+/// ```
+#[macro_export]
+macro_rules! dur {
+	($s:expr $(,)?) => {
+		const { $crate::__parse_literal($s) }
+	};
+}
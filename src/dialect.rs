@@ -0,0 +1,173 @@
+//! Pluggable duration syntaxes.
+//!
+//! A [Dialect] is a pair of a parser and a formatter for one syntax, such as
+//! [struct@Default], the syntax of [parse] and [Display], or [Go], the
+//! syntax of Go's `time.Duration`.
+//! Every syntax this crate supports has a dialect here, and other crates can
+//! add their own by implementing [Dialect] on a type of their own.
+//!
+//! A dialect can be used anywhere a syntax is picked at compile time:
+//! - [Duration::display_as] formats a duration in it.
+//! - With the `serde` feature, `Serde` de/serializes a duration in it with
+//!   `#[serde(with = "dur::dialect::Serde::<D>")]`.
+//! - With the `clap` feature, `ClapParser` parses command line arguments in it
+//!   with `#[arg(value_parser = dur::dialect::ClapParser::<D>::new())]`.
+//!
+//! #### Examples
+//! ```rust
+//! use core::fmt;
+//!
+//! use dur::{
+//!     dialect::{
+//!         Dialect,
+//!         Go,
+//!     },
+//!     Duration,
+//!     Error,
+//! };
+//!
+//! assert_eq!(Go::parse("1h30m"), Ok(Duration::from_mins(90)));
+//! assert_eq!(Duration::from_mins(90).display_as::<Go>().to_string(), "1h30m0s");
+//!
+//! /// Whole seconds as a plain number.
+//! struct Seconds;
+//!
+//! impl Dialect for Seconds {
+//!     fn parse(input: &str) -> Result<Duration, Error> {
+//!         dur::parse(&format!("{input}s"))
+//!     }
+//!
+//!     fn format(d: Duration, f: &mut impl fmt::Write) -> fmt::Result {
+//!         write!(f, "{}", d.as_secs())
+//!     }
+//! }
+//!
+//! assert_eq!(Seconds::parse("90"), Ok(Duration::from_secs(90)));
+//! assert_eq!(Duration::from_mins(2).display_as::<Seconds>().to_string(), "120");
+//! ```
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "clap")]
+pub use crate::clap_arg::ClapParser;
+#[cfg(feature = "serde")]
+pub use crate::serde_impl::Serde;
+use crate::*;
+
+/// A duration syntax, with a parser and a formatter.
+///
+/// The formatter should write something the parser accepts, though it doesn't
+/// have to be lossless.
+pub trait Dialect {
+	/// Parses a duration in this syntax.
+	fn parse(input: &str) -> Result<Duration, Error>;
+
+	/// Writes a duration in this syntax.
+	fn format(d: Duration, f: &mut impl fmt::Write) -> fmt::Result;
+}
+
+/// Implements [Display] with a [Dialect].
+///
+/// The only way to obtain a value of this struct is via the [display_as](Duration::display_as) method on [Duration].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DisplayAs<D> {
+	pub(crate) d: Duration,
+	pub(crate) dialect: PhantomData<D>,
+}
+
+impl<D: Dialect> Display for DisplayAs<D> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		D::format(self.d, f)
+	}
+}
+
+/// The syntax of [parse] and the [Display] implementation of
+/// [Duration], such as `1h 30m`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Default;
+
+impl Dialect for Default {
+	fn parse(input: &str) -> Result<Duration, Error> {
+		crate::parse(input)
+	}
+
+	fn format(d: Duration, f: &mut impl fmt::Write) -> fmt::Result {
+		write!(f, "{d}")
+	}
+}
+
+macro_rules! dialect {
+	($(#[$attr:meta])* $name:ident, $parse:ident, $format:ident) => {
+		$(#[$attr])*
+		#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+		pub struct $name;
+
+		impl Dialect for $name {
+			fn parse(input: &str) -> Result<Duration, Error> {
+				crate::$parse(input)
+			}
+
+			fn format(d: Duration, f: &mut impl fmt::Write) -> fmt::Result {
+				write!(f, "{}", d.$format())
+			}
+		}
+	};
+}
+
+dialect!(
+	/// The syntax of [parse](crate::parse), formatted losslessly like
+	/// [ExactDisplay].
+	Exact,
+	parse,
+	format_exact
+);
+dialect!(
+	/// Go's `time.Duration`; see [parse_go] and [GoDisplay].
+	Go,
+	parse_go,
+	format_go
+);
+dialect!(
+	/// systemd time spans; see [parse_systemd] and [SystemdDisplay].
+	Systemd,
+	parse_systemd,
+	format_systemd
+);
+dialect!(
+	/// PostgreSQL intervals; see [parse_postgres] and [PostgresDisplay].
+	Postgres,
+	parse_postgres,
+	format_postgres
+);
+dialect!(
+	/// Prometheus durations; see [parse_prometheus] and [PrometheusDisplay].
+	Prometheus,
+	parse_prometheus,
+	format_prometheus
+);
+dialect!(
+	/// The `humantime` crate; see [parse_humantime] and [HumantimeDisplay].
+	Humantime,
+	parse_humantime,
+	format_humantime
+);
+dialect!(
+	/// .NET's `TimeSpan` in the constant (`"c"`) format; see [parse_dotnet]
+	/// and [DotnetDisplay].
+	Dotnet,
+	parse_dotnet,
+	format_dotnet
+);
+dialect!(
+	/// .NET's `TimeSpan` in the general short (`"g"`) format; see
+	/// [parse_dotnet] and [DotnetGeneralDisplay].
+	DotnetGeneral,
+	parse_dotnet,
+	format_dotnet_general
+);
+dialect!(
+	/// Java's `Duration`; see [parse_java] and [JavaDisplay].
+	Java,
+	parse_java,
+	format_java
+);
@@ -0,0 +1,224 @@
+use crate::*;
+
+/// The largest .NET `TimeSpan`, `TimeSpan.MaxValue`, in ticks.
+const TIMESPAN_MAX: u128 = i64::MAX as u128;
+const TICKS_PER_SECOND: u128 = SECOND / TICK;
+
+/// Implements [Display] in the constant (`"c"`) format of .NET's `TimeSpan`,
+/// which `TimeSpan.ToString()` uses.
+///
+/// The format is `[d.]hh:mm:ss[.fffffff]`, such as `1.02:03:04.5000000` or
+/// `00:01:30`: the days are left out if there are none and the fraction, if
+/// any, is always 7 digits.
+/// .NET counts ticks of 100 nanoseconds, so shorter durations are truncated.
+/// Durations longer than `TimeSpan.MaxValue` are formatted the same way, with
+/// more days.
+///
+/// The only way to obtain a value of this struct is via the [format_dotnet](Duration::format_dotnet) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// let d = Duration::from_millis(93_784_500);
+/// assert_eq!(d.format_dotnet().to_string(), "1.02:03:04.5000000");
+/// assert_eq!(Duration::from_secs(90).format_dotnet().to_string(), "00:01:30");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DotnetDisplay(pub(crate) u128);
+
+/// Implements [Display] in the general short (`"g"`) format of .NET's
+/// `TimeSpan` with the invariant culture.
+///
+/// The format is `[d:]h:mm:ss[.FFFFFFF]`, such as `1:2:03:04.5` or `0:01:30`:
+/// the days are left out if there are none, the hours aren't padded and the
+/// fraction has no trailing zeros.
+/// Like with [DotnetDisplay], shorter durations than a tick are truncated.
+///
+/// The only way to obtain a value of this struct is via the [format_dotnet_general](Duration::format_dotnet_general) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// let d = Duration::from_millis(93_784_500);
+/// assert_eq!(d.format_dotnet_general().to_string(), "1:2:03:04.5");
+/// assert_eq!(Duration::from_secs(90).format_dotnet_general().to_string(), "0:01:30");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DotnetGeneralDisplay(pub(crate) u128);
+
+/// Splits nanoseconds into days, hours, minutes, seconds and ticks.
+fn split(n: u128) -> (u128, u128, u128, u128, u128) {
+	let secs = n / SECOND;
+	(
+		secs / 86400,
+		secs / 3600 % 24,
+		secs / 60 % 60,
+		secs % 60,
+		n % SECOND / TICK,
+	)
+}
+
+impl Display for DotnetDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let (days, hours, mins, secs, ticks) = split(self.0);
+		if days != 0 {
+			write!(f, "{days}.")?;
+		}
+		write!(f, "{hours:02}:{mins:02}:{secs:02}")?;
+		if ticks != 0 {
+			write!(f, ".{ticks:07}")?;
+		}
+		Ok(())
+	}
+}
+
+impl Display for DotnetGeneralDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let (days, hours, mins, secs, mut ticks) = split(self.0);
+		if days != 0 {
+			write!(f, "{days}:")?;
+		}
+		write!(f, "{hours}:{mins:02}:{secs:02}")?;
+		if ticks != 0 {
+			let mut digits = 7;
+			while ticks % 10 == 0 {
+				ticks /= 10;
+				digits -= 1;
+			}
+			write!(f, ".{ticks:0digits$}")?;
+		}
+		Ok(())
+	}
+}
+
+/// Parses a .NET `TimeSpan` in the constant (`"c"`) or general (`"g"` and
+/// `"G"`) formats.
+///
+/// The accepted formats are `[-][d.]hh:mm:ss[.fffffff]`, which
+/// `TimeSpan.ToString()` produces, and `[-][d:]h:mm:ss[.FFFFFFF]` with the
+/// invariant culture, such as `1.02:03:04.5000000`, `1:2:03:04.5` or
+/// `00:01:30`.
+/// The hours, minutes and seconds are one or two digits each, the fraction is
+/// at most 7 digits and whitespace around the value is ignored.
+///
+/// #### Errors
+/// Returns an error if the input isn't in one of the formats above, if the
+/// hours, minutes or seconds are out of range, such as `24:00:00`, or if it's
+/// longer than `TimeSpan.MaxValue`, about 29,227 years.
+/// Since a [Duration] can't be negative, a negative value, such as
+/// `-00:00:01`, is an [ErrorKind::IsNegative] holding the value in
+/// nanoseconds.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(dur::parse_dotnet("1.02:03:04.5"), Ok(Duration::from_millis(93_784_500)));
+/// assert_eq!(dur::parse_dotnet("1:2:03:04.5"), Ok(Duration::from_millis(93_784_500)));
+/// assert_eq!(dur::parse_dotnet("00:01:30"), Ok(Duration::from_secs(90)));
+/// assert!(dur::parse_dotnet("01:30").is_err());
+/// ```
+pub fn parse_dotnet(input: &str) -> Result<Duration, Error> {
+	parse_dotnet_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn parse_dotnet_spanned(input: &str) -> Result<Duration, Error> {
+	let s = input.as_bytes();
+	let start = input.len() - input.trim_start().len();
+	let end = input.trim_end().len().max(start);
+	let everything = start..end;
+	if start == end {
+		return Err(Error::new(ErrorKind::InvalidDuration, 0..input.len()));
+	}
+	let invalid_char = |i: usize| {
+		let len = input[i..].chars().next().map_or(0, char::len_utf8);
+		Error::new(ErrorKind::InvalidDuration, i..i + len)
+	};
+
+	let neg = s[start] == b'-';
+	let mut i = start + usize::from(neg);
+
+	// The numbers and the separator after each, `0` for the last.
+	let mut fields = [(0, 0); 5];
+	let mut seps = [0; 5];
+	let mut n = 0;
+	loop {
+		let digits = s[i..end].iter().take_while(|c| c.is_ascii_digit()).count();
+		if digits == 0 {
+			// `1:02:03.` ends with a separator.
+			return Err(if i == end {
+				Error::new(ErrorKind::InvalidDuration, i - 1..i)
+			} else {
+				invalid_char(i)
+			});
+		}
+		if n == fields.len() {
+			return Err(Error::new(ErrorKind::InvalidDuration, everything));
+		}
+		fields[n] = (i, i + digits);
+		i += digits;
+		n += 1;
+		if i == end {
+			break;
+		}
+		if !matches!(s[i], b':' | b'.') {
+			return Err(invalid_char(i));
+		}
+		seps[n - 1] = s[i];
+		i += 1;
+	}
+	let field = |j: usize| fields[j].0..fields[j].1;
+	let (days, time, frac) = match &seps[..n] {
+		[b':', b':', 0] => (None, 0, None),
+		[b':', b':', b'.', 0] => (None, 0, Some(3)),
+		[b'.' | b':', b':', b':', 0] => (Some(0), 1, None),
+		[b'.' | b':', b':', b':', b'.', 0] => (Some(0), 1, Some(4)),
+		_ => return Err(Error::new(ErrorKind::InvalidDuration, everything)),
+	};
+
+	let too_big = Error::new(ErrorKind::ValueTooBig, everything.clone());
+	let mut ticks = match days {
+		Some(d) => input[field(d)]
+			.parse::<u128>()
+			.ok()
+			.filter(|&d| d <= TIMESPAN_MAX)
+			.ok_or(too_big.clone())?,
+		None => 0,
+	};
+	for (j, max) in [(time, 24), (time + 1, 60), (time + 2, 60)] {
+		let field = field(j);
+		if field.len() > 2 {
+			return Err(Error::new(ErrorKind::InvalidDuration, field));
+		}
+		let n = input[field.clone()].parse::<u128>().unwrap_or_default();
+		if n >= max {
+			return Err(Error::new(ErrorKind::ValueTooBig, field));
+		}
+		ticks = ticks * max + n;
+	}
+	ticks *= TICKS_PER_SECOND;
+	if let Some(j) = frac {
+		let field = field(j);
+		if field.len() > 7 {
+			return Err(Error::new(ErrorKind::InvalidDuration, field));
+		}
+		let n = input[field.clone()].parse::<u128>().unwrap_or_default();
+		ticks += n * 10_u128.pow(7 - field.len() as u32);
+	}
+
+	if neg {
+		// `TimeSpan.MinValue` is one tick longer than `TimeSpan.MaxValue`.
+		if ticks > TIMESPAN_MAX + 1 {
+			return Err(too_big);
+		}
+		if ticks != 0 {
+			let n = -Decimal::from(ticks * TICK);
+			return Err(Error::new(ErrorKind::IsNegative(n), everything));
+		}
+	} else if ticks > TIMESPAN_MAX {
+		return Err(too_big);
+	}
+	Ok(Duration(ticks * TICK))
+}
@@ -0,0 +1,208 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::boxed::Box;
+use core::ops::Range;
+
+use crate::*;
+
+/// The kind of an [Error].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// Catch-all for values that aren't proper durations.
+	InvalidDuration,
+	/// The value being parsed is too big in nanoseconds in total to fit in a
+	/// [u128] or bigger than [Decimal::MAX] in case of a single unit.
+	ValueTooBig,
+	/// The value being parsed is missing a unit.
+	///
+	/// Note that values without any unit and only one number, such as `"42"`
+	/// are not errors and are parsed as milliseconds.
+	MissingUnit,
+	/// The value being parsed contains negative durations.
+	IsNegative(Decimal),
+	/// The value contains an unrecognized duration unit.
+	InvalidUnit,
+	/// The result of an [eval]uated expression is negative.
+	Underflow,
+	/// An [eval]uated expression divides by zero.
+	DivisionByZero,
+	/// The expression passed to [eval] is malformed or mixes numbers and
+	/// durations in an unsupported way, such as `1h + 2`.
+	InvalidExpression,
+	/// The value isn't a whole number of nanoseconds, with
+	/// [Precision::Exact].
+	PrecisionLoss,
+	/// The value has months or years, which don't have a fixed length, such
+	/// as a PostgreSQL interval passed to [parse_postgres].
+	CalendarUnit,
+	/// The duration is outside of the range of a [BoundedDuration].
+	OutOfRange {
+		/// The smallest duration allowed.
+		min: Duration,
+		/// The largest duration allowed.
+		max: Duration,
+	},
+	/// The duration isn't a whole number of the unit of a [Whole].
+	NotWhole(Unit),
+}
+
+impl Display for ErrorKind {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::InvalidDuration => write!(f, "invalid duration"),
+			Self::ValueTooBig => write!(f, "the duration value is too big to store"),
+			Self::MissingUnit => write!(f, "missing unit after number"),
+			Self::IsNegative(d) => write!(f, "durations cannot be negative ({d})"),
+			Self::InvalidUnit => write!(f, "invalid duration unit"),
+			Self::Underflow => write!(f, "the resulting duration is negative"),
+			Self::DivisionByZero => write!(f, "division by zero"),
+			Self::InvalidExpression => write!(f, "invalid duration expression"),
+			Self::PrecisionLoss => write!(f, "the duration has a fraction of a nanosecond"),
+			Self::CalendarUnit => write!(f, "months and years don't have a fixed length"),
+			Self::OutOfRange { min, max } => {
+				write!(f, "the duration must be between {min} and {max}")
+			}
+			Self::NotWhole(unit) => {
+				write!(
+					f,
+					"the duration must be a whole number of {}s",
+					unit.long_name()
+				)
+			}
+		}
+	}
+}
+
+/// The parse error.
+///
+/// Holds the [ErrorKind], the byte range of the offending part of the input
+/// and, for [ErrorKind::InvalidUnit], a suggested unit.
+/// With the `alloc` feature, it also keeps a copy of the input so that the
+/// offending part can be shown in the message; the API is the same either way.
+///
+/// #### Examples
+/// ```rust
+/// use dur::ErrorKind;
+///
+/// let e = dur::parse("1h 5 minuts").unwrap_err();
+/// assert_eq!(e.kind(), ErrorKind::InvalidUnit);
+/// assert_eq!(e.span(), 5..11);
+/// assert_eq!(e.suggestion(), Some("minutes"));
+///
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(e.fragment(), Some("minuts"));
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(
+///     e.to_string(),
+///     "invalid duration unit `minuts`, did you mean `minutes`?"
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Error {
+	kind: ErrorKind,
+	start: usize,
+	end: usize,
+	suggestion: Option<&'static str>,
+	#[cfg(feature = "alloc")]
+	input: Option<Box<str>>,
+}
+
+impl Error {
+	/// Creates an error of the given kind, caused by the bytes at `span` in
+	/// the input.
+	pub const fn new(kind: ErrorKind, span: Range<usize>) -> Self {
+		Self {
+			kind,
+			start: span.start,
+			end: span.end,
+			suggestion: None,
+			#[cfg(feature = "alloc")]
+			input: None,
+		}
+	}
+
+	/// Sets the suggested replacement for the offending part of the input.
+	pub const fn with_suggestion(mut self, suggestion: Option<&'static str>) -> Self {
+		self.suggestion = suggestion;
+		self
+	}
+
+	/// Stores a copy of the input with the `alloc` feature; otherwise, does
+	/// nothing.
+	pub fn with_input(self, input: &str) -> Self {
+		#[cfg(feature = "alloc")]
+		return Self {
+			input: Some(input.into()),
+			..self
+		};
+		#[cfg(not(feature = "alloc"))]
+		{
+			let _ = input;
+			self
+		}
+	}
+
+	/// Moves the span `n` bytes to the right, for errors from parsing a
+	/// substring.
+	pub(crate) const fn offset(mut self, n: usize) -> Self {
+		self.start += n;
+		self.end += n;
+		self
+	}
+
+	/// Returns the kind of this error.
+	pub const fn kind(&self) -> ErrorKind {
+		self.kind
+	}
+
+	/// Returns the byte range of the part of the input that caused this error.
+	pub const fn span(&self) -> Range<usize> {
+		self.start..self.end
+	}
+
+	/// Returns a known unit that is similar to an unrecognized one, such as
+	/// `minutes` for `minuts`.
+	pub const fn suggestion(&self) -> Option<&'static str> {
+		self.suggestion
+	}
+
+	/// Returns the input that caused this error.
+	///
+	/// Always returns [None] without the `alloc` feature.
+	pub fn input(&self) -> Option<&str> {
+		#[cfg(feature = "alloc")]
+		return self.input.as_deref();
+		#[cfg(not(feature = "alloc"))]
+		None
+	}
+
+	/// Returns the part of the input that caused this error, i.e. the
+	/// [span](Self::span) of the [input](Self::input).
+	///
+	/// Always returns [None] without the `alloc` feature.
+	pub fn fragment(&self) -> Option<&str> {
+		self.input().and_then(|s| s.get(self.span()))
+	}
+}
+
+impl From<ErrorKind> for Error {
+	/// Creates an error with an empty span.
+	fn from(kind: ErrorKind) -> Self {
+		Self::new(kind, 0..0)
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match (self.kind, self.fragment()) {
+			(ErrorKind::InvalidUnit, Some(unit)) => write!(f, "invalid duration unit `{unit}`")?,
+			(kind, _) => Display::fmt(&kind, f)?,
+		}
+		match self.suggestion {
+			Some(s) => write!(f, ", did you mean `{s}`?"),
+			None => Ok(()),
+		}
+	}
+}
+
+impl core::error::Error for Error {}
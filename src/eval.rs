@@ -0,0 +1,377 @@
+use core::ops::Range;
+
+use crate::*;
+
+#[derive(Copy, Clone, Debug)]
+enum Value {
+	Num(Decimal),
+	/// A duration in nanoseconds, which can have a fractional part until the
+	/// final result.
+	Dur(Decimal),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Tok<'a> {
+	Value(Value),
+	Ident(&'a str),
+	Op(char),
+	End,
+}
+
+/// A token and the byte range of the input it spans.
+type Spanned<T> = (T, Range<usize>);
+
+struct Lexer<'a> {
+	input: &'a str,
+	s: &'a str,
+	peeked: Option<Spanned<Tok<'a>>>,
+}
+
+fn skip_space(s: &str) -> &str {
+	s.trim_start()
+}
+
+impl<'a> Lexer<'a> {
+	/// Returns the byte offset of `s`, a suffix of the input.
+	fn pos(&self, s: &str) -> usize {
+		self.input.len() - s.len()
+	}
+
+	fn peek(&mut self) -> Result<Spanned<Tok<'a>>, Error> {
+		if let Some(t) = &self.peeked {
+			return Ok(t.clone());
+		}
+		self.s = skip_space(self.s);
+		let start = self.pos(self.s);
+		let t = self.lex()?;
+		let t = (t, start..self.pos(self.s));
+		self.peeked = Some(t.clone());
+		Ok(t)
+	}
+
+	fn next(&mut self) -> Result<Spanned<Tok<'a>>, Error> {
+		let t = self.peek()?;
+		self.peeked = None;
+		Ok(t)
+	}
+
+	fn expect(&mut self, op: char) -> Result<(), Error> {
+		match self.next()? {
+			(Tok::Op(c), _) if c == op => Ok(()),
+			(_, span) => Err(Error::new(ErrorKind::InvalidExpression, span)),
+		}
+	}
+
+	fn lex(&mut self) -> Result<Tok<'a>, Error> {
+		let Some(c) = self.s.chars().next() else {
+			return Ok(Tok::End);
+		};
+
+		if c.is_ascii_digit() || c == '.' {
+			return self.literal().map(Tok::Value);
+		}
+
+		if c.is_alphabetic() {
+			let len = self
+				.s
+				.find(|c: char| !c.is_alphabetic())
+				.unwrap_or(self.s.len());
+			let (ident, rem) = self.s.split_at(len);
+			self.s = rem;
+			return Ok(Tok::Ident(ident));
+		}
+
+		if "+-*/(),".contains(c) {
+			self.s = &self.s[c.len_utf8()..];
+			return Ok(Tok::Op(c));
+		}
+
+		let start = self.pos(self.s);
+		Err(Error::new(
+			ErrorKind::InvalidExpression,
+			start..start + c.len_utf8(),
+		))
+	}
+
+	/// Lexes a number or a duration literal.
+	///
+	/// A duration literal is a number followed by a unit, optionally followed
+	/// by more such pairs, e.g. `1h 30m`.
+	fn literal(&mut self) -> Result<Value, Error> {
+		let start = self.pos(self.s);
+		let (rem, n) = decimal(self.s)
+			.map_err(|_| Error::new(ErrorKind::InvalidExpression, start..start + 1))?;
+		let n = parse_dec(n).map_err(|kind| Error::new(kind, start..self.pos(rem)))?;
+
+		let rem = skip_space(rem);
+		if !rem.starts_with(char::is_alphabetic) {
+			self.s = rem;
+			return Ok(Value::Num(n));
+		}
+
+		let (mut rem, unit) = parse_unit(rem).map_err(|e| e.offset(self.pos(rem)))?;
+		let mut total = mul_unit(n, unit).map_err(|kind| Error::new(kind, start..self.pos(rem)))?;
+
+		// Consume subsequent `N UNIT` pairs.
+		while let Ok((r, n)) = decimal(skip_space(rem)) {
+			let r = skip_space(r);
+			if !r.starts_with(char::is_alphabetic) {
+				break;
+			}
+			let number = self.pos(skip_space(rem))..self.pos(r.trim_end());
+			let n = parse_dec(n).map_err(|kind| Error::new(kind, number.clone()))?;
+			let (r, unit) = parse_unit(r).map_err(|e| e.offset(self.pos(r)))?;
+			let too_big = || Error::new(ErrorKind::ValueTooBig, number.start..self.pos(r));
+			total = total
+				.checked_add(mul_unit(n, unit).map_err(|_| too_big())?)
+				.ok_or_else(too_big)?;
+			rem = r;
+		}
+
+		self.s = rem;
+		Ok(Value::Dur(total))
+	}
+}
+
+fn mul_unit(n: Decimal, unit: u128) -> Result<Decimal, ErrorKind> {
+	Decimal::from(unit)
+		.checked_mul(n)
+		.ok_or(ErrorKind::ValueTooBig)
+}
+
+/// How deeply parentheses, `min`/`max` and unary operators can be nested,
+/// so that untrusted input can't overflow the stack.
+const MAX_DEPTH: u32 = 128;
+
+struct Parser<'a> {
+	lex: Lexer<'a>,
+	depth: u32,
+}
+
+impl Parser<'_> {
+	/// Enters a nested subexpression started by the token at `span`.
+	fn enter(&mut self, span: Range<usize>) -> Result<(), Error> {
+		self.depth += 1;
+		if self.depth > MAX_DEPTH {
+			return Err(Error::new(ErrorKind::InvalidExpression, span));
+		}
+		Ok(())
+	}
+
+	fn expr(&mut self) -> Result<Spanned<Value>, Error> {
+		let mut lhs = self.term()?;
+		loop {
+			match self.lex.peek()? {
+				(Tok::Op(op @ ('+' | '-')), _) => {
+					self.lex.next()?;
+					let rhs = self.term()?;
+					lhs = apply(op, lhs, rhs)?;
+				}
+				_ => return Ok(lhs),
+			}
+		}
+	}
+
+	fn term(&mut self) -> Result<Spanned<Value>, Error> {
+		let mut lhs = self.unary()?;
+		loop {
+			match self.lex.peek()? {
+				(Tok::Op(op @ ('*' | '/')), _) => {
+					self.lex.next()?;
+					let rhs = self.unary()?;
+					lhs = apply(op, lhs, rhs)?;
+				}
+				_ => return Ok(lhs),
+			}
+		}
+	}
+
+	fn unary(&mut self) -> Result<Spanned<Value>, Error> {
+		match self.lex.peek()? {
+			(Tok::Op('+'), op) => {
+				self.lex.next()?;
+				self.enter(op.clone())?;
+				let (v, span) = self.unary()?;
+				self.depth -= 1;
+				Ok((v, op.start..span.end))
+			}
+			(Tok::Op('-'), op) => {
+				self.lex.next()?;
+				self.enter(op.clone())?;
+				let (v, span) = self.unary()?;
+				self.depth -= 1;
+				let span = op.start..span.end;
+				match v {
+					Value::Num(n) => Ok((Value::Num(-n), span)),
+					Value::Dur(d) if d.is_zero() => Ok((Value::Dur(d), span)),
+					Value::Dur(_) => Err(Error::new(ErrorKind::Underflow, span)),
+				}
+			}
+			_ => self.primary(),
+		}
+	}
+
+	fn primary(&mut self) -> Result<Spanned<Value>, Error> {
+		match self.lex.next()? {
+			(Tok::Value(v), span) => Ok((v, span)),
+			(Tok::Op('('), open) => {
+				self.enter(open.clone())?;
+				let (v, _) = self.expr()?;
+				self.lex.expect(')')?;
+				self.depth -= 1;
+				let end = self.lex.pos(self.lex.s);
+				Ok((v, open.start..end))
+			}
+			(Tok::Ident(name), ident) => {
+				let is_max = if name.eq_ignore_ascii_case("min") {
+					false
+				} else if name.eq_ignore_ascii_case("max") {
+					true
+				} else {
+					return Err(Error::new(ErrorKind::InvalidExpression, ident));
+				};
+
+				self.enter(ident.clone())?;
+				self.lex.expect('(')?;
+				let (mut acc, _) = self.expr()?;
+				loop {
+					match self.lex.next()? {
+						(Tok::Op(')'), close) => {
+							self.depth -= 1;
+							return Ok((acc, ident.start..close.end));
+						}
+						(Tok::Op(','), _) => {
+							let (v, span) = self.expr()?;
+							acc = min_max(is_max, acc, v)
+								.map_err(|kind| Error::new(kind, ident.start..span.end))?;
+						}
+						(_, span) => return Err(Error::new(ErrorKind::InvalidExpression, span)),
+					}
+				}
+			}
+			(_, span) => Err(Error::new(ErrorKind::InvalidExpression, span)),
+		}
+	}
+}
+
+fn to_dur(d: Decimal) -> Result<Value, ErrorKind> {
+	if d.is_sign_negative() && !d.is_zero() {
+		return Err(ErrorKind::Underflow);
+	}
+	Ok(Value::Dur(d))
+}
+
+fn min_max(is_max: bool, a: Value, b: Value) -> Result<Value, ErrorKind> {
+	match (a, b) {
+		(Value::Num(a), Value::Num(b)) => Ok(Value::Num(if is_max { a.max(b) } else { a.min(b) })),
+		(Value::Dur(a), Value::Dur(b)) => Ok(Value::Dur(if is_max { a.max(b) } else { a.min(b) })),
+		_ => Err(ErrorKind::InvalidExpression),
+	}
+}
+
+/// Applies `op`; errors span both operands.
+fn apply(
+	op: char,
+	(lhs, l): Spanned<Value>,
+	(rhs, r): Spanned<Value>,
+) -> Result<Spanned<Value>, Error> {
+	let span = l.start..r.end;
+	apply_values(op, lhs, rhs)
+		.map(|v| (v, span.clone()))
+		.map_err(|kind| Error::new(kind, span))
+}
+
+fn apply_values(op: char, lhs: Value, rhs: Value) -> Result<Value, ErrorKind> {
+	use Value::*;
+
+	match (op, lhs, rhs) {
+		('+', Dur(a), Dur(b)) => a.checked_add(b).map(Dur).ok_or(ErrorKind::ValueTooBig),
+		('-', Dur(a), Dur(b)) => a
+			.checked_sub(b)
+			.ok_or(ErrorKind::ValueTooBig)
+			.and_then(to_dur),
+		('+', Num(a), Num(b)) => a.checked_add(b).map(Num).ok_or(ErrorKind::ValueTooBig),
+		('-', Num(a), Num(b)) => a.checked_sub(b).map(Num).ok_or(ErrorKind::ValueTooBig),
+
+		('*', Num(a), Num(b)) => a.checked_mul(b).map(Num).ok_or(ErrorKind::ValueTooBig),
+		('*', Dur(d), Num(n)) | ('*', Num(n), Dur(d)) => d
+			.checked_mul(n)
+			.ok_or(ErrorKind::ValueTooBig)
+			.and_then(to_dur),
+
+		('/', _, Num(n)) if n.is_zero() => Err(ErrorKind::DivisionByZero),
+		('/', _, Dur(d)) if d.is_zero() => Err(ErrorKind::DivisionByZero),
+		('/', Num(a), Num(b)) => a.checked_div(b).map(Num).ok_or(ErrorKind::ValueTooBig),
+		('/', Dur(d), Num(n)) => d
+			.checked_div(n)
+			.ok_or(ErrorKind::ValueTooBig)
+			.and_then(to_dur),
+		('/', Dur(a), Dur(b)) => a.checked_div(b).map(Num).ok_or(ErrorKind::ValueTooBig),
+
+		_ => Err(ErrorKind::InvalidExpression),
+	}
+}
+
+/// Evaluates an arithmetic expression over durations.
+///
+/// Durations use the same syntax as [parse] (`1h 30m`, `.5s`, ...) and can be
+/// combined with the following:
+/// - `+` and `-` between two durations.
+/// - `*` and `/` between a duration and a plain (possibly decimal) number.
+/// - `/` between two durations, which yields their ratio as a plain number.
+/// - Parentheses for grouping.
+/// - `min(a, b, ...)` and `max(a, b, ...)`.
+///
+/// Fractions of a nanosecond are kept until the end, so `0.5ns + 0.5ns` is
+/// `1ns`; only the result is truncated to whole nanoseconds.
+/// Unlike [parse], an expression that evaluates to a plain number is an
+/// error ([ErrorKind::MissingUnit]).
+/// Negative results are reported with [ErrorKind::Underflow] and results that
+/// don't fit in a [Duration] with [ErrorKind::ValueTooBig].
+/// The [span](Error::span) of an error covers the offending token or
+/// subexpression.
+/// Nesting parentheses, `min`/`max` or unary operators more than 128 levels
+/// deep is an error ([ErrorKind::InvalidExpression]).
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ErrorKind};
+///
+/// assert_eq!(dur::eval("2 * 1h + 30m"), Ok(Duration::from_secs(2 * 3600 + 30 * 60)));
+/// assert_eq!(dur::eval("(1d - 4h) / 4"), Ok(Duration::from_secs(5 * 3600)));
+/// assert_eq!(dur::eval("1h / 30m * 5s"), Ok(Duration::from_secs(10)));
+/// assert_eq!(dur::eval("max(1h, 90m) * 1.5"), Ok(Duration::from_secs(135 * 60)));
+///
+/// let e = dur::eval("5m + (1h - 2h)").unwrap_err();
+/// assert_eq!(e.kind(), ErrorKind::Underflow);
+/// assert_eq!(e.span(), 6..13);
+/// assert_eq!(dur::eval("1h / 0").map_err(|e| e.kind()), Err(ErrorKind::DivisionByZero));
+/// assert_eq!(dur::eval("1h + 2").map_err(|e| e.kind()), Err(ErrorKind::InvalidExpression));
+/// ```
+pub fn eval(input: &str) -> Result<Duration, Error> {
+	eval_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn eval_spanned(input: &str) -> Result<Duration, Error> {
+	let mut p = Parser {
+		lex: Lexer {
+			input,
+			s: input,
+			peeked: None,
+		},
+		depth: 0,
+	};
+
+	let (v, span) = p.expr()?;
+	match p.lex.next()? {
+		(Tok::End, _) => (),
+		(_, span) => return Err(Error::new(ErrorKind::InvalidExpression, span)),
+	}
+
+	match v {
+		Value::Dur(n) => u128::try_from(n.trunc())
+			.map(Duration)
+			.map_err(|_| Error::new(ErrorKind::ValueTooBig, span)),
+		Value::Num(_) => Err(Error::new(ErrorKind::MissingUnit, span)),
+	}
+}
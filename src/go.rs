@@ -0,0 +1,248 @@
+use crate::*;
+
+/// The largest absolute value of a Go `time.Duration`, in nanoseconds.
+const GO_MAX: u128 = 1 << 63;
+
+/// The units accepted by Go's `time.ParseDuration`, case-sensitive.
+const GO_UNITS: [(&str, u64); 8] = [
+	("ns", 1),
+	("us", 1_000),
+	("µs", 1_000),
+	("μs", 1_000),
+	("ms", 1_000_000),
+	("s", 1_000_000_000),
+	("m", 60_000_000_000),
+	("h", 3_600_000_000_000),
+];
+
+/// Implements [Display] in the format of Go's `time.Duration.String`.
+///
+/// The output matches Go byte-for-byte, such as `1h2m3.5s`, `1.5µs` or `0s`:
+/// there are no spaces, hours are the largest unit and durations shorter than
+/// a second use the largest unit that fits.
+/// Durations longer than Go's limit of about 292 years are formatted the same
+/// way, with more hours.
+///
+/// The only way to obtain a value of this struct is via the [format_go](Duration::format_go) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(Duration::from_millis(3723_500).format_go().to_string(), "1h2m3.5s");
+/// assert_eq!(Duration::from_nanos(1500).format_go().to_string(), "1.5µs");
+/// assert_eq!(Duration::ZERO.format_go().to_string(), "0s");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct GoDisplay(pub(crate) u128);
+
+/// Writes `n / 10^digits` without trailing zeros after the decimal point.
+fn write_frac(f: &mut Formatter, n: u128, mut digits: usize) -> fmt::Result {
+	let pow = 10_u128.pow(digits as u32);
+	let (int, mut frac) = (n / pow, n % pow);
+	write!(f, "{int}")?;
+	if frac == 0 {
+		return Ok(());
+	}
+	while frac % 10 == 0 {
+		frac /= 10;
+		digits -= 1;
+	}
+	write!(f, ".{frac:0digits$}")
+}
+
+impl Display for GoDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let n = self.0;
+		if n == 0 {
+			f.write_str("0s")
+		} else if n < MICROSECOND {
+			write!(f, "{n}ns")
+		} else if n < MILLISECOND {
+			write_frac(f, n, 3)?;
+			f.write_str("µs")
+		} else if n < SECOND {
+			write_frac(f, n, 6)?;
+			f.write_str("ms")
+		} else {
+			let secs = n / SECOND;
+			let (hours, mins) = (secs / 3600, secs / 60 % 60);
+			if hours != 0 {
+				write!(f, "{hours}h{mins}m")?;
+			} else if mins != 0 {
+				write!(f, "{mins}m")?;
+			}
+			write_frac(f, secs % 60 * SECOND + n % SECOND, 9)?;
+			f.write_str("s")
+		}
+	}
+}
+
+/// Parses a duration in the format of Go's `time.ParseDuration`.
+///
+/// The grammar is exactly Go's: a possibly signed sequence of decimal numbers,
+/// each with an optional fraction and a unit suffix, with no spaces, such as
+/// `300ms`, `1.5h` or `2h45m`.
+/// The units are `ns`, `us` (or `µs`), `ms`, `s`, `m` and `h`, and a lone `0`
+/// is the only number allowed without a unit.
+/// Fractions are rounded the way Go does, so the results are identical.
+///
+/// #### Errors
+/// Returns an error if the input isn't a valid Go duration or if it's out of
+/// the range of Go's `time.Duration`, as in Go.
+/// Since a [Duration] can't be negative, a negative value, such as `-5s`, is
+/// an [ErrorKind::IsNegative] holding the value in nanoseconds; `-0s` is
+/// fine.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ErrorKind};
+///
+/// assert_eq!(dur::parse_go("1h2m3.5s"), Ok(Duration::from_millis(3723_500)));
+/// assert_eq!(dur::parse_go("1.5µs"), Ok(Duration::from_nanos(1500)));
+///
+/// // Spaces and units Go doesn't know aren't allowed.
+/// assert!(dur::parse_go("1h 2m").is_err());
+/// assert!(dur::parse_go("1d").is_err());
+/// assert_eq!(
+///     dur::parse_go("9223372036854775808ns").map_err(|e| e.kind()),
+///     Err(ErrorKind::ValueTooBig)
+/// );
+/// ```
+pub fn parse_go(input: &str) -> Result<Duration, Error> {
+	parse_go_spanned(input).map_err(|e| e.with_input(input))
+}
+
+/// Parses an integer the way Go's `leadingInt` does, returning it and the
+/// number of digits, or [None] if it's bigger than [GO_MAX].
+fn leading_int(s: &[u8]) -> Option<(u64, usize)> {
+	let mut x = 0_u64;
+	let mut i = 0;
+	while let Some(&c) = s.get(i).filter(|c| c.is_ascii_digit()) {
+		if x > (GO_MAX / 10) as u64 {
+			return None;
+		}
+		x = x * 10 + u64::from(c - b'0');
+		if u128::from(x) > GO_MAX {
+			return None;
+		}
+		i += 1;
+	}
+	Some((x, i))
+}
+
+/// Parses a fraction the way Go's `leadingFraction` does, returning its
+/// digits as an integer, the scale and the number of digits.
+///
+/// The digits that would overflow are ignored.
+fn leading_fraction(s: &[u8]) -> (u64, f64, usize) {
+	let (mut x, mut scale) = (0_u64, 1.0);
+	let mut overflow = false;
+	let mut i = 0;
+	while let Some(&c) = s.get(i).filter(|c| c.is_ascii_digit()) {
+		i += 1;
+		if overflow {
+			continue;
+		}
+		if x > i64::MAX as u64 / 10 {
+			overflow = true;
+			continue;
+		}
+		let y = x * 10 + u64::from(c - b'0');
+		if u128::from(y) > GO_MAX {
+			overflow = true;
+			continue;
+		}
+		x = y;
+		scale *= 10.0;
+	}
+	(x, scale, i)
+}
+
+fn parse_go_spanned(input: &str) -> Result<Duration, Error> {
+	let everything = 0..input.len();
+	let s = input.as_bytes();
+
+	let neg = matches!(s.first(), Some(b'-'));
+	let mut i = usize::from(matches!(s.first(), Some(b'-' | b'+')));
+	if &s[i..] == b"0" {
+		return Ok(Duration(0));
+	}
+	if i == s.len() {
+		return Err(Error::new(ErrorKind::InvalidDuration, everything));
+	}
+
+	let mut total = 0_u128;
+	while i < s.len() {
+		let start = i;
+		// The next character must be [0-9.].
+		if !(s[i] == b'.' || s[i].is_ascii_digit()) {
+			let len = input[i..].chars().next().map_or(1, char::len_utf8);
+			return Err(Error::new(ErrorKind::InvalidDuration, i..i + len));
+		}
+
+		// [0-9]*
+		let digits = s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+		let (mut v, len) =
+			leading_int(&s[i..]).ok_or(Error::new(ErrorKind::ValueTooBig, i..i + digits))?;
+		i += len;
+		let pre = len != 0;
+
+		// (\.[0-9]*)?
+		let (mut frac, mut scale, mut post) = (0, 1.0, false);
+		if s.get(i) == Some(&b'.') {
+			i += 1;
+			let len;
+			(frac, scale, len) = leading_fraction(&s[i..]);
+			i += len;
+			post = len != 0;
+		}
+		if !pre && !post {
+			// No digits, as in `.s`.
+			return Err(Error::new(ErrorKind::InvalidDuration, start..i));
+		}
+
+		// The unit is everything up to the next number.
+		let len = s[i..]
+			.iter()
+			.take_while(|&&c| !(c == b'.' || c.is_ascii_digit()))
+			.count();
+		if len == 0 {
+			return Err(Error::new(ErrorKind::MissingUnit, start..i));
+		}
+		let unit = GO_UNITS
+			.iter()
+			.find(|(u, _)| u.as_bytes() == &s[i..i + len])
+			.map(|&(_, n)| n)
+			.ok_or(Error::new(ErrorKind::InvalidUnit, i..i + len))?;
+		i += len;
+
+		let too_big = Error::new(ErrorKind::ValueTooBig, start..i);
+		if u128::from(v) > GO_MAX / u128::from(unit) {
+			return Err(too_big);
+		}
+		v *= unit;
+		if frac > 0 {
+			// Go uses floating point arithmetic here, which can round.
+			v += (frac as f64 * (unit as f64 / scale)) as u64;
+			if u128::from(v) > GO_MAX {
+				return Err(too_big);
+			}
+		}
+		total += u128::from(v);
+		if total > GO_MAX {
+			return Err(too_big);
+		}
+	}
+
+	if neg {
+		if total != 0 {
+			// The total is at most 2^63, which a Decimal can hold.
+			let n = -Decimal::from(total);
+			return Err(Error::new(ErrorKind::IsNegative(n), everything));
+		}
+	} else if total == GO_MAX {
+		return Err(Error::new(ErrorKind::ValueTooBig, everything));
+	}
+	Ok(Duration(total))
+}
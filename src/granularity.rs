@@ -0,0 +1,38 @@
+//! Units for [Whole](crate::Whole), such as [Second] or [Millisecond].
+
+use core::{
+	fmt::Debug,
+	hash::Hash,
+};
+
+use crate::Unit;
+
+/// The unit a [Whole](crate::Whole) duration is a whole number of.
+pub trait Granularity: Copy + Debug + Eq + Ord + Hash + Send + Sync + 'static {
+	/// The unit.
+	const UNIT: Unit;
+}
+
+macro_rules! granularity {
+	($($name:ident),*) => {$(
+		#[doc = concat!("Whole [", stringify!($name), "s](Unit::", stringify!($name), ").")]
+		#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+		pub struct $name;
+
+		impl Granularity for $name {
+			const UNIT: Unit = Unit::$name;
+		}
+	)*};
+}
+
+granularity!(
+	Nanosecond,
+	Microsecond,
+	Millisecond,
+	Second,
+	Minute,
+	Hour,
+	Day,
+	Week,
+	Year
+);
@@ -0,0 +1,380 @@
+use crate::*;
+
+/// Returns `ceil(log2(2 * 10^digits))`; the number of bits needed to keep
+/// `digits` significant decimal digits in every bucket.
+pub(crate) const fn sub_bits(digits: u8) -> u32 {
+	match digits {
+		1 => 5,
+		2 => 8,
+		3 => 11,
+		4 => 15,
+		5 => 18,
+		_ => panic!("the number of significant digits must be between 1 and 5"),
+	}
+}
+
+const fn index_of(sub_bits: u32, n: u128) -> usize {
+	let bits = 128 - (n | ((1 << sub_bits) - 1)).leading_zeros();
+	let shift = bits - sub_bits;
+	(shift as u128 * (1 << (sub_bits - 1)) + (n >> shift)) as usize
+}
+
+/// Returns the lowest value and the width of the bucket at `i`.
+const fn range_of(sub_bits: u32, i: usize) -> (u128, u128) {
+	let i = i as u128;
+	let half = 1 << (sub_bits - 1);
+	if i < 1 << sub_bits {
+		(i, 1)
+	} else {
+		let shift = i / half - 1;
+		((i - shift * half) << shift, 1 << shift)
+	}
+}
+
+/// A fixed-size histogram of durations with log-linear buckets, in the style
+/// of [HdrHistogram](http://hdrhistogram.org/).
+///
+/// Values are grouped into buckets whose width is proportional to their
+/// magnitude, so that every bucket keeps a configurable number of
+/// significant decimal digits.
+/// `N` is the number of buckets; use [capacity_for](Self::capacity_for) to
+/// calculate how many you need to cover values up to a given maximum.
+/// Values that don't fit are counted in the last bucket; [max](Self::max) is
+/// still tracked exactly.
+///
+/// The histogram doesn't allocate, so it works without `alloc`.
+/// With the `serde` feature, it can be serialized and deserialized.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, DurationHistogram};
+///
+/// const CAP: usize = DurationHistogram::<0>::capacity_for(2, Duration::SECOND);
+/// let mut h = DurationHistogram::<CAP>::new(2);
+///
+/// for us in 1..=1000 {
+///     h.record(Duration::from_micros(us));
+/// }
+///
+/// let p99 = h.quantile(0.99).unwrap();
+/// assert!(p99 > Duration::from_micros(985) && p99 < Duration::from_micros(995));
+/// assert_eq!(h.max(), Some(Duration::from_millis(1)));
+///
+/// // Print a table of the buckets.
+/// println!("{h}");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DurationHistogram<const N: usize> {
+	pub(crate) digits: u8,
+	sub_bits: u32,
+	pub(crate) count: u64,
+	pub(crate) min: u128,
+	pub(crate) max: u128,
+	pub(crate) counts: [u64; N],
+}
+
+/// A bucket of a [DurationHistogram].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HistogramBucket {
+	low: Duration,
+	high: Duration,
+	count: u64,
+}
+
+impl HistogramBucket {
+	/// Returns the smallest value that falls into this bucket.
+	pub const fn low(&self) -> Duration {
+		self.low
+	}
+
+	/// Returns the largest value that falls into this bucket.
+	pub const fn high(&self) -> Duration {
+		self.high
+	}
+
+	/// Returns the number of values recorded in this bucket.
+	pub const fn count(&self) -> u64 {
+		self.count
+	}
+}
+
+impl<const N: usize> DurationHistogram<N> {
+	/// Creates an empty histogram that keeps `significant_digits` significant
+	/// decimal digits of every value.
+	///
+	/// #### Panics
+	/// Panics if `significant_digits` is not between 1 and 5 or if `N` is too
+	/// small to hold the values below `2 * 10^significant_digits` (see
+	/// [capacity_for](Self::capacity_for)).
+	pub const fn new(significant_digits: u8) -> Self {
+		let sub_bits = sub_bits(significant_digits);
+		assert!(
+			N >= 1 << sub_bits,
+			"the histogram capacity is too small for this many significant digits"
+		);
+
+		Self {
+			digits: significant_digits,
+			sub_bits,
+			count: 0,
+			min: u128::MAX,
+			max: 0,
+			counts: [0; N],
+		}
+	}
+
+	/// Returns the number of buckets needed to record values up to `max`
+	/// with `significant_digits` significant decimal digits.
+	///
+	/// #### Panics
+	/// Panics if `significant_digits` is not between 1 and 5.
+	pub const fn capacity_for(significant_digits: u8, max: Duration) -> usize {
+		index_of(sub_bits(significant_digits), max.0) + 1
+	}
+
+	/// Returns the number of significant decimal digits this histogram keeps.
+	pub const fn significant_digits(&self) -> u8 {
+		self.digits
+	}
+
+	pub(crate) fn index(&self, n: u128) -> usize {
+		index_of(self.sub_bits, n).min(N - 1)
+	}
+
+	fn bucket(&self, i: usize) -> HistogramBucket {
+		let (low, width) = range_of(self.sub_bits, i);
+		let mut high = low.saturating_add(width - 1);
+		if i == N - 1 {
+			// The last bucket also holds the values that didn't fit.
+			high = high.max(self.max);
+		}
+
+		HistogramBucket {
+			low: Duration(low),
+			high: Duration(high),
+			count: self.counts[i],
+		}
+	}
+
+	/// Records a duration.
+	pub fn record(&mut self, d: impl Into<Duration>) {
+		self.record_n(d, 1);
+	}
+
+	/// Records a duration `n` times.
+	pub fn record_n(&mut self, d: impl Into<Duration>, n: u64) {
+		if n == 0 {
+			return;
+		}
+		let d = d.into().0;
+		let i = self.index(d);
+		self.counts[i] = self.counts[i].saturating_add(n);
+		self.count = self.count.saturating_add(n);
+		self.min = self.min.min(d);
+		self.max = self.max.max(d);
+	}
+
+	/// Adds the values recorded in `other` to `self`.
+	///
+	/// This is exact if both histograms keep the same number of significant
+	/// digits; otherwise values from `other` are re-recorded at the midpoint
+	/// of their bucket, or at the minimum or maximum for the outermost ones.
+	pub fn merge<const M: usize>(&mut self, other: &DurationHistogram<M>) {
+		if other.count == 0 {
+			return;
+		}
+
+		let first = other.counts.iter().position(|&n| n != 0);
+		let last = other.counts.iter().rposition(|&n| n != 0);
+		for (i, &n) in other.counts.iter().enumerate() {
+			if n == 0 {
+				continue;
+			}
+			if self.sub_bits == other.sub_bits {
+				let i = i.min(N - 1);
+				self.counts[i] = self.counts[i].saturating_add(n);
+			} else {
+				// Keep the minimum and maximum within the outermost buckets.
+				let (low, width) = range_of(other.sub_bits, i);
+				let mid = if Some(i) == first {
+					other.min
+				} else if Some(i) == last {
+					other.max
+				} else {
+					low.saturating_add(width / 2).clamp(other.min, other.max)
+				};
+				let j = self.index(mid);
+				self.counts[j] = self.counts[j].saturating_add(n);
+			}
+		}
+
+		self.count = self.count.saturating_add(other.count);
+		self.min = self.min.min(other.min);
+		self.max = self.max.max(other.max);
+	}
+
+	/// Removes all the recorded values.
+	pub fn clear(&mut self) {
+		*self = Self::new(self.digits);
+	}
+
+	/// Returns the number of recorded values.
+	pub const fn count(&self) -> u64 {
+		self.count
+	}
+
+	/// Returns true if no value has been recorded.
+	pub const fn is_empty(&self) -> bool {
+		self.count == 0
+	}
+
+	/// Returns the smallest recorded duration, or [None] if the histogram is
+	/// empty.
+	pub const fn min(&self) -> Option<Duration> {
+		if self.count == 0 {
+			None
+		} else {
+			Some(Duration(self.min))
+		}
+	}
+
+	/// Returns the largest recorded duration, or [None] if the histogram is
+	/// empty.
+	pub const fn max(&self) -> Option<Duration> {
+		if self.count == 0 {
+			None
+		} else {
+			Some(Duration(self.max))
+		}
+	}
+
+	/// Returns an approximation of the value at quantile `q`, or [None] if
+	/// the histogram is empty.
+	///
+	/// `q` is clamped into the range `0.0..=1.0`; `0.5` is the median, `0.99`
+	/// the 99th percentile.
+	/// The result is the midpoint of the bucket holding the quantile, kept
+	/// between [min](Self::min) and [max](Self::max), or [max](Self::max) if
+	/// it's in the last bucket.
+	pub fn quantile(&self, q: f64) -> Option<Duration> {
+		if self.count == 0 {
+			return None;
+		}
+
+		// The nearest-rank method: `ceil(q * count)`.
+		let rank = q.clamp(0.0, 1.0) * self.count as f64;
+		let rank = if (rank as u64) as f64 == rank {
+			rank as u64
+		} else {
+			rank as u64 + 1
+		};
+		let rank = rank.clamp(1, self.count);
+
+		let mut seen = 0_u64;
+		for (i, &n) in self.counts.iter().enumerate() {
+			seen = seen.saturating_add(n);
+			if seen >= rank && i == N - 1 {
+				// The last bucket also holds the values that didn't fit.
+				return Some(Duration(self.max));
+			} else if seen >= rank {
+				let (low, width) = range_of(self.sub_bits, i);
+				let mid = low.saturating_add(width / 2);
+				return Some(Duration(mid.clamp(self.min, self.max)));
+			}
+		}
+
+		Some(Duration(self.max))
+	}
+
+	/// Returns an iterator over the buckets that have at least one value in
+	/// them, in ascending order.
+	pub fn iter(&self) -> impl Iterator<Item = HistogramBucket> + '_ {
+		self.counts
+			.iter()
+			.enumerate()
+			.filter(|(_, &n)| n != 0)
+			.map(|(i, _)| self.bucket(i))
+	}
+}
+
+/// A fixed-capacity string buffer, used for padding formatted values.
+pub(crate) struct Buf {
+	buf: [u8; 64],
+	len: usize,
+}
+
+impl Buf {
+	pub(crate) const fn new() -> Self {
+		Self {
+			buf: [0; 64],
+			len: 0,
+		}
+	}
+
+	pub(crate) fn as_str(&self) -> &str {
+		// Only complete `str`s are ever written.
+		core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+	}
+}
+
+impl fmt::Write for Buf {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let end = self.len + s.len();
+		if end > self.buf.len() {
+			return Err(fmt::Error);
+		}
+		self.buf[self.len..end].copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
+	}
+}
+
+impl<const N: usize> Display for DurationHistogram<N> {
+	/// Prints a table of the non-empty buckets, e.g.
+	///
+	/// ```text
+	///          low         high      count  cumulative
+	///     983.04us       1.01ms          5      50.00%
+	///       1.96ms       2.03ms          5     100.00%
+	/// ```
+	///
+	/// The precision flag is forwarded to each [Duration].
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		use fmt::Write;
+
+		writeln!(
+			f,
+			"{:>12} {:>12} {:>10} {:>11}",
+			"low", "high", "count", "cumulative"
+		)?;
+
+		let mut seen = 0_u64;
+		for b in self.iter() {
+			seen = seen.saturating_add(b.count);
+
+			let mut low = Buf::new();
+			let mut high = Buf::new();
+			match f.precision() {
+				Some(p) => {
+					write!(low, "{:.p$}", b.low)?;
+					write!(high, "{:.p$}", b.high)?;
+				}
+				None => {
+					write!(low, "{}", b.low)?;
+					write!(high, "{}", b.high)?;
+				}
+			}
+
+			writeln!(
+				f,
+				"{:>12} {:>12} {:>10} {:>10.2}%",
+				low.as_str(),
+				high.as_str(),
+				b.count,
+				seen as f64 * 100.0 / self.count as f64,
+			)?;
+		}
+
+		Ok(())
+	}
+}
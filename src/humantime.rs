@@ -0,0 +1,244 @@
+use core::ops::Range;
+
+use crate::*;
+
+/// A month of the `humantime` crate, 30.44 days, in seconds.
+const HUMANTIME_MONTH: u64 = 2_630_016;
+/// A year of the `humantime` crate, 365.25 days, in seconds.
+const HUMANTIME_YEAR: u64 = 31_557_600;
+
+/// Implements [Display] like the `humantime` crate's `format_duration`.
+///
+/// Every non-zero part down to nanoseconds is written, such as
+/// `1h 2m 3s 4ms`, with `year`, `month` and `day` spelled out, such as
+/// `1year 2months 3days`.
+/// A month is 30.44 days, a year is 365.25 days and zero is `0s`.
+///
+/// See [parse_humantime] for how it differs from the [Display] implementation
+/// of [Duration].
+///
+/// The only way to obtain a value of this struct is via the [format_humantime](Duration::format_humantime) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// let d = Duration::from_millis(3_723_004);
+/// assert_eq!(d.format_humantime().to_string(), "1h 2m 3s 4ms");
+/// assert_eq!(Duration::from_days(3).format_humantime().to_string(), "3days");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HumantimeDisplay(pub(crate) u128);
+
+impl Display for HumantimeDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let (secs, nanos) = (self.0 / SECOND, self.0 % SECOND);
+		if self.0 == 0 {
+			return f.write_str("0s");
+		}
+
+		let (years, secs) = (secs / HUMANTIME_YEAR as u128, secs % HUMANTIME_YEAR as u128);
+		let (months, secs) = (
+			secs / HUMANTIME_MONTH as u128,
+			secs % HUMANTIME_MONTH as u128,
+		);
+		let parts = [
+			(years, "year", true),
+			(months, "month", true),
+			(secs / 86400, "day", true),
+			(secs % 86400 / 3600, "h", false),
+			(secs % 3600 / 60, "m", false),
+			(secs % 60, "s", false),
+			(nanos / MILLISECOND, "ms", false),
+			(nanos / MICROSECOND % 1000, "us", false),
+			(nanos % 1000, "ns", false),
+		];
+
+		let mut first = true;
+		for (n, unit, plural) in parts {
+			if n == 0 {
+				continue;
+			}
+			if !first {
+				f.write_str(" ")?;
+			}
+			first = false;
+			write!(f, "{n}{unit}")?;
+			if plural && n > 1 {
+				f.write_str("s")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Parses a duration like the `humantime` crate's `parse_duration`.
+///
+/// The syntax is a sequence of whole numbers followed by a unit, where the
+/// spaces between the parts are optional.
+/// The units are case-sensitive:
+///
+/// - `nanos`, `nsec`, `ns`
+/// - `usec`, `us`
+/// - `millis`, `msec`, `ms`
+/// - `seconds`, `second`, `secs`, `sec`, `s`
+/// - `minutes`, `minute`, `mins`, `min`, `m`
+/// - `hours`, `hour`, `hrs`, `hr`, `h`
+/// - `days`, `day`, `d`
+/// - `weeks`, `week`, `w`
+/// - `months`, `month`, `M`, which are 30.44 days
+/// - `years`, `year`, `y`, which are 365.25 days
+///
+/// Like in `humantime`, the total can't be more than [u64::MAX] seconds.
+///
+/// #### Differences from [parse]
+/// | Input      | [parse]              | `parse_humantime`             |
+/// |------------|----------------------|-------------------------------|
+/// | `500`      | 500 milliseconds     | Error, the unit is missing    |
+/// | `1M`       | A minute             | A month, 30.44 days           |
+/// | `2 months` | Error                | 2 months                      |
+/// | `1H`       | An hour              | Error, units are case-sensitive |
+/// | `1.5h`     | 1 hour 30 minutes    | Error, no fractions           |
+/// | `1h,30m`   | 1 hour 30 minutes    | Error, no commas              |
+/// | `1yr`      | A year               | Error                         |
+/// | `1µs`      | A microsecond        | Error                         |
+/// | `1 0s`     | Error                | 10 seconds                    |
+///
+/// And between [Display] and [HumantimeDisplay]:
+///
+/// | Duration                 | [Display]  | [HumantimeDisplay] |
+/// |--------------------------|------------|--------------------|
+/// | 1 hour 2 minutes 3.004 s | `1h 2m 3s` | `1h 2m 3s 4ms`     |
+/// | 3 days                   | `3d`       | `3days`            |
+/// | 45 days                  | `45d`      | `1month 14days 13h 26m 24s` |
+///
+/// #### Errors
+/// Returns an error if the input isn't a valid duration for `humantime`.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(dur::parse_humantime("1h 2m 3s"), Ok(Duration::from_secs(3723)));
+/// assert_eq!(dur::parse_humantime("1M"), Ok(Duration::from_secs(2_630_016)));
+/// assert!(dur::parse_humantime("500").is_err());
+/// ```
+pub fn parse_humantime(input: &str) -> Result<Duration, Error> {
+	parse_humantime_spanned(input).map_err(|e| e.with_input(input))
+}
+
+/// Returns the span of the character at `i`.
+fn char_span(s: &str, i: usize) -> Range<usize> {
+	i..i + s[i..].chars().next().map_or(0, char::len_utf8)
+}
+
+/// Skips whitespace from `i`, returning the value of the digit after it, or
+/// [None] at the end of the input.
+fn first_digit(s: &str, i: &mut usize) -> Result<Option<u64>, Error> {
+	for c in s[*i..].chars() {
+		let at = *i;
+		*i += c.len_utf8();
+		match c {
+			'0'..='9' => return Ok(Some(u64::from(c as u8 - b'0'))),
+			_ if c.is_whitespace() => (),
+			_ => return Err(Error::new(ErrorKind::InvalidDuration, char_span(s, at))),
+		}
+	}
+	Ok(None)
+}
+
+/// Returns the unit in seconds and nanoseconds.
+fn unit(s: &str) -> Option<(u64, u64)> {
+	Some(match s {
+		"nanos" | "nsec" | "ns" => (0, 1),
+		"usec" | "us" => (0, 1_000),
+		"millis" | "msec" | "ms" => (0, 1_000_000),
+		"seconds" | "second" | "secs" | "sec" | "s" => (1, 0),
+		"minutes" | "minute" | "min" | "mins" | "m" => (60, 0),
+		"hours" | "hour" | "hr" | "hrs" | "h" => (3600, 0),
+		"days" | "day" | "d" => (86400, 0),
+		"weeks" | "week" | "w" => (86400 * 7, 0),
+		"months" | "month" | "M" => (HUMANTIME_MONTH, 0),
+		"years" | "year" | "y" => (HUMANTIME_YEAR, 0),
+		_ => return None,
+	})
+}
+
+fn parse_humantime_spanned(input: &str) -> Result<Duration, Error> {
+	let mut i = 0;
+	let Some(mut n) = first_digit(input, &mut i)? else {
+		return Err(Error::new(ErrorKind::InvalidDuration, 0..input.len()));
+	};
+	let (mut secs, mut nanos) = (0_u64, 0_u64);
+
+	loop {
+		// The number, which can have spaces in it, up to the unit.
+		let number_start = i - 1;
+		let mut start = input.len();
+		for (j, c) in input[i..].char_indices() {
+			let at = i + j;
+			match c {
+				'0'..='9' => {
+					n = n
+						.checked_mul(10)
+						.and_then(|n| n.checked_add(u64::from(c as u8 - b'0')))
+						.ok_or(Error::new(ErrorKind::ValueTooBig, number_start..at + 1))?;
+				}
+				_ if c.is_whitespace() => (),
+				'a'..='z' | 'A'..='Z' => {
+					start = at;
+					break;
+				}
+				_ => return Err(Error::new(ErrorKind::InvalidDuration, char_span(input, at))),
+			}
+		}
+
+		// The unit, up to whitespace or the next number.
+		let end = start
+			+ input[start..]
+				.find(|c: char| !c.is_ascii_alphabetic())
+				.unwrap_or(input.len() - start);
+		i = end;
+		let next = input[end..].chars().next();
+		match next {
+			Some(c) if c.is_ascii_digit() || c.is_whitespace() => i += c.len_utf8(),
+			Some(_) => {
+				return Err(Error::new(
+					ErrorKind::InvalidDuration,
+					char_span(input, end),
+				))
+			}
+			None => (),
+		}
+
+		let (s, ns) = match unit(&input[start..end]) {
+			Some(u) => u,
+			None if start == end => {
+				return Err(Error::new(ErrorKind::MissingUnit, number_start..end));
+			}
+			None => return Err(Error::new(ErrorKind::InvalidUnit, start..end)),
+		};
+		let too_big = Error::new(ErrorKind::ValueTooBig, number_start..end);
+		let part = n
+			.checked_mul(s)
+			.and_then(|s| s.checked_add(secs))
+			.zip(n.checked_mul(ns).and_then(|ns| ns.checked_add(nanos)))
+			.and_then(|(s, ns)| {
+				s.checked_add(ns / 1_000_000_000)
+					.map(|s| (s, ns % 1_000_000_000))
+			})
+			.ok_or(too_big)?;
+		(secs, nanos) = part;
+
+		n = match next {
+			// `1h2m`
+			Some(c) if c.is_ascii_digit() => u64::from(c as u8 - b'0'),
+			_ => match first_digit(input, &mut i)? {
+				Some(n) => n,
+				None => break,
+			},
+		};
+	}
+
+	Ok(Duration(u128::from(secs) * SECOND + u128::from(nanos)))
+}
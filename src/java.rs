@@ -0,0 +1,203 @@
+use crate::*;
+
+/// The designators of a Java `Duration` and their length in seconds, in the
+/// order they must be written.
+const UNITS: [(u8, i64); 4] = [(b'D', 86400), (b'H', 3600), (b'M', 60), (b'S', 1)];
+
+/// The range of a Java `Duration`, in nanoseconds.
+const JAVA_MIN: i128 = i64::MIN as i128 * SECOND as i128;
+const JAVA_MAX: i128 = i64::MAX as i128 * SECOND as i128 + (SECOND as i128 - 1);
+
+/// Implements [Display] in the format of Java's `Duration.toString`, an ISO
+/// 8601 duration such as `PT8H6M12.345S`.
+///
+/// The output matches Java: hours are the largest unit, the fraction of a
+/// second has no trailing zeros and zero is `PT0S`.
+///
+/// The only way to obtain a value of this struct is via the [format_java](Duration::format_java) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// let d = Duration::from_millis(29_172_345);
+/// assert_eq!(d.format_java().to_string(), "PT8H6M12.345S");
+/// assert_eq!(Duration::from_days(2).format_java().to_string(), "PT48H");
+/// assert_eq!(Duration::ZERO.format_java().to_string(), "PT0S");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct JavaDisplay(pub(crate) u128);
+
+impl Display for JavaDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let (secs, mut nanos) = (self.0 / SECOND, self.0 % SECOND);
+		let (hours, mins) = (secs / 3600, secs / 60 % 60);
+		f.write_str("PT")?;
+		if hours != 0 {
+			write!(f, "{hours}H")?;
+		}
+		if mins != 0 {
+			write!(f, "{mins}M")?;
+		}
+		if secs % 60 == 0 && nanos == 0 && (hours != 0 || mins != 0) {
+			return Ok(());
+		}
+
+		write!(f, "{}", secs % 60)?;
+		if nanos != 0 {
+			let mut digits = 9;
+			while nanos % 10 == 0 {
+				nanos /= 10;
+				digits -= 1;
+			}
+			write!(f, ".{nanos:0digits$}")?;
+		}
+		f.write_str("S")
+	}
+}
+
+/// Parses a duration in the format of Java's `Duration.parse`, an ISO 8601
+/// duration such as `PT8H6M12.345S`, `P2DT3H4M` or `PT15M`.
+///
+/// The grammar is exactly Java's: `[-+]P[nD][T[nH][nM][n[.f]S]]`, where a day
+/// is 24 hours, each number can have a sign and only the seconds can have a
+/// fraction, of at most 9 digits and after a `.` or a `,`.
+/// The letters are case-insensitive and there must be at least one part.
+///
+/// #### Errors
+/// Returns an error if the input isn't a valid Java duration or if it's out of
+/// the range of Java's `Duration`, as in Java.
+/// Since a [Duration] can't be negative, a negative value, such as `-PT6H` or
+/// `PT-6H3M`, is an [ErrorKind::IsNegative] holding the value in nanoseconds.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ErrorKind};
+///
+/// assert_eq!(dur::parse_java("PT8H6M12.345S"), Ok(Duration::from_millis(29_172_345)));
+/// assert_eq!(dur::parse_java("P2D"), Ok(Duration::from_days(2)));
+/// // 6 hours and minus 3 minutes.
+/// assert_eq!(dur::parse_java("-PT-6H+3M"), Ok(Duration::from_mins(357)));
+///
+/// let e = dur::parse_java("PT-6H3M").unwrap_err();
+/// assert!(matches!(e.kind(), ErrorKind::IsNegative(_)));
+/// assert!(dur::parse_java("P1W").is_err());
+/// ```
+pub fn parse_java(input: &str) -> Result<Duration, Error> {
+	parse_java_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn parse_java_spanned(input: &str) -> Result<Duration, Error> {
+	let everything = 0..input.len();
+	let s = input.as_bytes();
+	let invalid_char = |i: usize| {
+		let len = input[i..].chars().next().map_or(0, char::len_utf8);
+		Error::new(ErrorKind::InvalidDuration, i..i + len)
+	};
+
+	let neg = s.first() == Some(&b'-');
+	let mut i = usize::from(matches!(s.first(), Some(b'-' | b'+')));
+	match s.get(i) {
+		Some(b'P' | b'p') => i += 1,
+		Some(_) => return Err(invalid_char(i)),
+		None => return Err(Error::new(ErrorKind::InvalidDuration, everything)),
+	}
+
+	let mut values = [0_i64; 4];
+	let mut nanos = 0_i64;
+	let (mut next, mut parts, mut time) = (0, 0, None);
+	while i < s.len() {
+		if matches!(s[i], b'T' | b't') && time.is_none() {
+			time = Some(parts);
+			next = 1;
+			i += 1;
+			continue;
+		}
+
+		// [-+]?[0-9]+([.,][0-9]{0,9})?
+		let start = i;
+		if matches!(s[i], b'-' | b'+') {
+			i += 1;
+		}
+		let digits = s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+		if digits == 0 {
+			return Err(invalid_char(i.min(s.len() - 1)));
+		}
+		i += digits;
+		let number = start..i;
+		let frac = match s.get(i) {
+			Some(b'.' | b',') => {
+				let digits = s[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+				i += 1 + digits;
+				Some(i - digits..i)
+			}
+			_ => None,
+		};
+
+		let Some(&c) = s.get(i) else {
+			return Err(Error::new(ErrorKind::MissingUnit, start..i));
+		};
+		let Some(p) = UNITS.iter().position(|(u, _)| *u == c.to_ascii_uppercase()) else {
+			return Err(if c.is_ascii_alphabetic() {
+				Error::new(ErrorKind::InvalidUnit, i..i + 1)
+			} else {
+				invalid_char(i)
+			});
+		};
+		i += 1;
+		// Days before the `T`, the rest after it in order and only seconds with
+		// a fraction.
+		if p < next || (p == 0) == time.is_some() || (frac.is_some() && p != 3) {
+			return Err(Error::new(ErrorKind::InvalidDuration, start..i));
+		}
+		next = p + 1;
+		parts += 1;
+
+		let too_big = Error::new(ErrorKind::ValueTooBig, start..i);
+		values[p] = input[number.clone()]
+			.parse::<i64>()
+			.ok()
+			.and_then(|n| n.checked_mul(UNITS[p].1))
+			.ok_or(too_big)?;
+		if let Some(frac) = frac {
+			if frac.len() > 9 {
+				return Err(Error::new(ErrorKind::InvalidDuration, frac));
+			}
+			nanos = input[frac.clone()].parse::<i64>().unwrap_or_default()
+				* 10_i64.pow(9 - frac.len() as u32);
+			if s[start] == b'-' {
+				nanos = -nanos;
+			}
+		}
+	}
+	// `P`, `PT` and `P1DT` aren't allowed.
+	if parts == 0 || time == Some(parts) {
+		return Err(Error::new(ErrorKind::InvalidDuration, everything));
+	}
+
+	// Java adds the parts up as a `long` from the seconds, so this overflows
+	// where it does.
+	let too_big = Error::new(ErrorKind::ValueTooBig, everything.clone());
+	let [days, hours, mins, secs] = values;
+	let secs = mins
+		.checked_add(secs)
+		.and_then(|n| hours.checked_add(n))
+		.and_then(|n| days.checked_add(n))
+		.ok_or(too_big.clone())?;
+	let mut total = i128::from(secs) * SECOND as i128 + i128::from(nanos);
+	if !(JAVA_MIN..=JAVA_MAX).contains(&total) {
+		return Err(too_big);
+	}
+	if neg {
+		total = -total;
+		if total > JAVA_MAX {
+			return Err(too_big);
+		}
+	}
+
+	if total < 0 {
+		let n = Decimal::from_i128_with_scale(total, 0);
+		return Err(Error::new(ErrorKind::IsNegative(n), everything));
+	}
+	Ok(Duration(total as u128))
+}
@@ -1,20 +1,42 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../readme.md")]
 
-#[cfg(feature = "alloc")]
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
 
 mod arithmetic_impls;
+mod bounded;
 #[cfg(feature = "clap")]
 mod clap_arg;
+mod const_parse;
+pub mod dialect;
+mod dotnet;
+mod error;
+mod eval;
 mod formatting;
+mod go;
+pub mod granularity;
+mod histogram;
+mod humantime;
+mod java;
+mod natural;
+pub mod nom;
+mod number;
+mod parser;
+mod postgres;
+mod prometheus;
 #[cfg(feature = "serde")]
 mod serde_impl;
+mod stats;
+mod systemd;
 #[cfg(test)]
 mod tests;
+mod timeout;
+#[cfg(feature = "std")]
+mod timer;
+mod unit;
+mod whole;
 
-#[cfg(all(feature = "alloc", not(feature = "std")))]
-use alloc::boxed::Box;
 #[doc(no_inline)]
 pub use core::time::Duration as StdDuration;
 use core::{
@@ -27,34 +49,88 @@ use core::{
 	str::FromStr,
 };
 
-pub use formatting::ExactDisplay;
-use nom::{
+use ::nom::{
 	branch::alt,
-	bytes::complete::{
-		tag,
-		tag_no_case,
-	},
-	character::complete::{
-		digit1,
-		one_of,
-		space0,
-	},
-	combinator::{
-		opt,
-		recognize,
-		success,
-		value,
-	},
+	bytes::complete::tag,
+	character::complete::digit1,
+	combinator::recognize,
 	sequence::{
 		pair,
 		separated_pair,
 	},
 };
+pub use bounded::BoundedDuration;
+pub use const_parse::{
+	__parse_literal,
+	parse_const,
+};
+pub use dotnet::{
+	parse_dotnet,
+	DotnetDisplay,
+	DotnetGeneralDisplay,
+};
+pub use error::{
+	Error,
+	ErrorKind,
+};
+pub use eval::eval;
+pub use formatting::ExactDisplay;
+pub use go::{
+	parse_go,
+	GoDisplay,
+};
+pub use histogram::{
+	DurationHistogram,
+	HistogramBucket,
+};
+pub use humantime::{
+	parse_humantime,
+	HumantimeDisplay,
+};
+pub use java::{
+	parse_java,
+	JavaDisplay,
+};
+pub use parser::{
+	CustomDisplay,
+	CustomUnit,
+	Parser,
+	Precision,
+};
+pub use postgres::{
+	parse_postgres,
+	PgInterval,
+	PostgresDisplay,
+};
+pub use prometheus::{
+	parse_prometheus,
+	PrometheusDisplay,
+};
 #[doc(no_inline)]
 pub use rust_decimal::{
 	self,
 	Decimal,
 };
+pub use stats::DurationStats;
+pub use systemd::{
+	parse_systemd,
+	SystemdDisplay,
+};
+pub use timeout::Timeout;
+#[cfg(feature = "std")]
+pub use timer::{
+	Clock,
+	Deadline,
+	ManualClock,
+	MonotonicClock,
+	Stopwatch,
+};
+pub use unit::Unit;
+pub use whole::{
+	Millis,
+	Seconds,
+	Whole,
+};
 
 /// A human readable duration backed by a [u128].
 ///
@@ -62,6 +138,8 @@ pub use rust_decimal::{
 #[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug, Hash, Default)]
 pub struct Duration(u128);
 
+/// A .NET tick.
+const TICK: u128 = 100;
 const MICROSECOND: u128 = 1000;
 const MILLISECOND: u128 = MICROSECOND * 1000;
 const SECOND: u128 = MILLISECOND * 1000;
@@ -71,49 +149,6 @@ const DAY: u128 = HOUR * 24;
 const WEEK: u128 = DAY * 7;
 const YEAR: u128 = SECOND * 31_557_600;
 
-// Error
-
-/// The parse error.
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Error {
-	/// Catch-all for values that aren't proper durations.
-	InvalidDuration,
-	/// The value being parsed is too big in nanoseconds in total to fit in a
-	/// [u128] or bigger than [Decimal::MAX] in case of a single unit.
-	ValueTooBig,
-	/// The value being parsed is missing a unit.
-	///
-	/// Note that values without any unit and only one number, such as `"42"`
-	/// are not errors and are parsed as milliseconds.
-	MissingUnit,
-	/// The value being parsed contains negative durations.
-	IsNegative(Decimal),
-	/// The value contains an unrecognized duration unit.
-	#[cfg(feature = "alloc")]
-	InvalidUnit(Box<str>),
-	#[cfg(not(feature = "alloc"))]
-	/// The value contains an unrecognized duration unit.
-	InvalidUnit,
-}
-
-impl Display for Error {
-	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		match self {
-			Self::InvalidDuration => write!(f, "invalid duration"),
-			Self::ValueTooBig => write!(f, "the duration value is too big to store"),
-			Self::MissingUnit => write!(f, "missing unit after number"),
-			Self::IsNegative(d) => write!(f, "durations cannot be negative ({d})"),
-			#[cfg(feature = "alloc")]
-			Self::InvalidUnit(s) => write!(f, "invalid duration unit `{s}`"),
-			#[cfg(not(feature = "alloc"))]
-			Self::InvalidUnit => write!(f, "invalid duration unit`"),
-		}
-	}
-}
-
-#[cfg(feature = "std")]
-impl std::error::Error for Error {}
-
 // Parsing
 
 fn to_dec(n: u128) -> Option<Decimal> {
@@ -127,181 +162,70 @@ fn to_dec(n: u128) -> Option<Decimal> {
 }
 
 fn parse_unit(input: &str) -> Result<(&str, u128), Error> {
-	if input.trim().is_empty() {
-		return Err(Error::MissingUnit);
-	}
-
-	let (rem, unit) = alt((
-		value(
-			1,
-			alt((
-				tag_no_case("nanoseconds"),
-				tag_no_case("nanosecond"),
-				tag_no_case("nanos"),
-				tag_no_case("ns"),
-			)),
-		),
-		value(
-			MICROSECOND,
-			alt((
-				tag_no_case("microseconds"),
-				tag_no_case("microsecond"),
-				tag_no_case("micros"),
-				tag_no_case("us"),
-				tag_no_case("µs"),
-			)),
-		),
-		value(
-			MILLISECOND,
-			alt((
-				tag_no_case("milliseconds"),
-				tag_no_case("millisecond"),
-				tag_no_case("millis"),
-				tag_no_case("ms"),
-			)),
-		),
-		value(
-			SECOND,
-			alt((
-				tag_no_case("seconds"),
-				tag_no_case("second"),
-				tag_no_case("secs"),
-				tag_no_case("sec"),
-				tag_no_case("s"),
-			)),
-		),
-		value(
-			MINUTE,
-			alt((
-				tag_no_case("minutes"),
-				tag_no_case("minute"),
-				tag_no_case("mins"),
-				tag_no_case("min"),
-				tag_no_case("m"),
-			)),
-		),
-		value(
-			HOUR,
-			alt((
-				tag_no_case("hours"),
-				tag_no_case("hour"),
-				tag_no_case("hrs"),
-				tag_no_case("hr"),
-				tag_no_case("h"),
-			)),
-		),
-		value(
-			DAY,
-			alt((tag_no_case("days"), tag_no_case("day"), tag_no_case("d"))),
-		),
-		value(
-			WEEK,
-			alt((tag_no_case("weeks"), tag_no_case("week"), tag_no_case("w"))),
-		),
-		value(
-			YEAR,
-			alt((
-				tag_no_case("years"),
-				tag_no_case("year"),
-				tag_no_case("yrs"),
-				tag_no_case("yr"),
-				tag_no_case("y"),
-			)),
-		),
-	))(input)
-	.map_err(|_: nom::Err<nom::error::Error<_>>| {
-		#[cfg(not(feature = "alloc"))]
-		return Error::InvalidUnit;
-		#[cfg(feature = "alloc")]
-		Error::InvalidUnit(
-			input
-				.split_whitespace()
-				.next()
-				.unwrap_or_else(|| input.trim())
-				.into(),
-		)
-	})?;
-
-	if rem.starts_with(|c: char| c.is_alphabetic()) {
-		#[cfg(feature = "alloc")]
-		return Err(Error::InvalidUnit(
-			input.split_whitespace().next().unwrap_or(input).into(),
-		));
-
-		#[cfg(not(feature = "alloc"))]
-		Err(Error::InvalidUnit)
-	} else {
-		Ok((rem, unit))
-	}
+	// Built-in units are whole nanoseconds.
+	Parser::new()
+		.parse_unit(input)
+		.map(|(rem, (nanos, _))| (rem, nanos))
 }
 
-#[doc = include_str!("fn.parse.md")]
-pub fn parse(input: &str) -> Result<Duration, Error> {
-	if input.trim().is_empty() {
-		return Err(Error::InvalidDuration);
-	}
-	if let Ok(d) = input.parse::<Decimal>() {
-		if d.is_sign_negative() {
-			return Err(Error::IsNegative(d));
-		}
-		return d
-			.checked_mul(Decimal::from(MILLISECOND))
-			.map(|d| Duration(u128::try_from(d).unwrap()))
-			.ok_or(Error::ValueTooBig);
-	}
-
-	let parse_decimal = alt((
+/// Recognizes an unsigned decimal number such as `1`, `1.5`, `1.` or `.5`.
+fn decimal(input: &str) -> ::nom::IResult<&str, &str> {
+	alt((
 		recognize(separated_pair(digit1, tag("."), digit1)),
 		recognize(pair(digit1, tag("."))),
 		recognize(pair(tag("."), digit1)),
 		digit1,
-	));
-
-	let mut parse_decimal = recognize(pair(opt(one_of("-+")), parse_decimal));
-
-	let mut sep = alt::<_, _, nom::error::Error<_>, _>((
-		recognize(pair(tag(","), space0)),
-		space0,
-		success(""),
-	));
-
-	let mut s = input;
-	let mut n = 0_u128;
-
-	for i in 0.. {
-		if i != 0 {
-			(s, _) = sep(s).unwrap();
-		}
-
-		if s.is_empty() {
-			break;
-		}
-
-		let (rem, d) =
-			parse_decimal(s).map_err(|_: nom::Err<nom::error::Error<_>>| Error::InvalidDuration)?;
+	))(input)
+}
 
-		let d = d.parse::<Decimal>().map_err(|e| match e {
-			rust_decimal::Error::ExceedsMaximumPossibleValue
-			| rust_decimal::Error::LessThanMinimumPossibleValue => Error::ValueTooBig,
-			_ => Error::InvalidDuration,
-		})?;
+fn parse_dec(s: &str) -> Result<Decimal, ErrorKind> {
+	s.parse::<Decimal>().map_err(|e| match e {
+		rust_decimal::Error::ExceedsMaximumPossibleValue
+		| rust_decimal::Error::LessThanMinimumPossibleValue => ErrorKind::ValueTooBig,
+		_ => ErrorKind::InvalidDuration,
+	})
+}
 
-		if d.is_sign_negative() {
-			return Err(Error::IsNegative(d));
-		}
+#[doc = include_str!("fn.parse.md")]
+pub fn parse(input: &str) -> Result<Duration, Error> {
+	Parser::new().parse(input)
+}
 
-		let rem = rem.trim_start_matches(|c: char| c == ' ' || c == '\t');
-		let (rem, unit) = parse_unit(rem)?;
-		let d = Decimal::from(unit)
-			.checked_mul(d)
-			.ok_or(Error::ValueTooBig)?;
-		n = n
-			.checked_add(d.try_into().unwrap())
-			.ok_or(Error::ValueTooBig)?;
-		s = rem;
-	}
+/// Like [parse], but also returns the fraction of a nanosecond that was
+/// truncated, in nanoseconds.
+///
+/// See [Parser::parse_with_report] for details and [Precision] for the other
+/// ways to handle sub-nanosecond values.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Decimal, Duration};
+///
+/// assert_eq!(
+///     dur::parse_with_report("1.0000000001s"),
+///     Ok((Duration::from_secs(1), Decimal::new(1, 1)))
+/// );
+/// ```
+pub fn parse_with_report(input: &str) -> Result<(Duration, Decimal), Error> {
+	Parser::new().parse_with_report(input)
+}
 
-	Ok(Duration(n))
+/// Parses a duration at the start of `input`, returning it and the rest of the
+/// input.
+///
+/// See [Parser::parse_prefix] for details and [nom] for combinators.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(
+///     dur::parse_prefix("5s) then wait 1m"),
+///     Ok((Duration::from_secs(5), ") then wait 1m"))
+/// );
+/// ```
+pub fn parse_prefix(input: &str) -> Result<(Duration, &str), Error> {
+	Parser::new().parse_prefix(input)
 }
 
 /// Parse the human-readable duration string into an [StdDuration].
@@ -340,12 +264,20 @@ impl From<Duration> for StdDuration {
 
 // Constants
 impl Duration {
+	pub const DAY: Self = Self(DAY);
 	pub const HOUR: Self = Self(HOUR);
+	/// The maximum duration, `u128::MAX` nanoseconds.
+	pub const MAX: Self = Self(u128::MAX);
 	pub const MICROSECOND: Self = Self(MICROSECOND);
 	pub const MILLISECOND: Self = Self(MILLISECOND);
 	pub const MINUTE: Self = Self(MINUTE);
 	pub const NANOSECOND: Self = Self(1);
 	pub const SECOND: Self = Self(SECOND);
+	pub const WEEK: Self = Self(WEEK);
+	/// A Julian year of 365.25 days.
+	pub const YEAR: Self = Self(YEAR);
+	/// A duration of zero time.
+	pub const ZERO: Self = Self(0);
 }
 
 // Impls
@@ -356,6 +288,16 @@ impl Duration {
 		Self(ns)
 	}
 
+	/// Creates a new `Duration` from the specified number of .NET ticks, which
+	/// are 100 nanoseconds, such as the `Ticks` of a `TimeSpan`.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn from_dotnet_ticks(ticks: u128) -> Self {
+		Self(ticks * TICK)
+	}
+
 	/// Creates a new `Duration` from the specified number of microseconds.
 	///
 	/// #### Overflow Behavior
@@ -383,6 +325,195 @@ impl Duration {
 		Self(secs * SECOND)
 	}
 
+	/// Creates a new `Duration` from the specified number of minutes.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn from_mins(mins: u128) -> Self {
+		Self(mins * MINUTE)
+	}
+
+	/// Creates a new `Duration` from the specified number of hours.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn from_hours(hours: u128) -> Self {
+		Self(hours * HOUR)
+	}
+
+	/// Creates a new `Duration` from the specified number of days.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn from_days(days: u128) -> Self {
+		Self(days * DAY)
+	}
+
+	/// Creates a new `Duration` from the specified number of weeks.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn from_weeks(weeks: u128) -> Self {
+		Self(weeks * WEEK)
+	}
+
+	const fn checked_from(n: u128, unit: u128) -> Option<Self> {
+		match n.checked_mul(unit) {
+			Some(n) => Some(Self(n)),
+			None => None,
+		}
+	}
+
+	/// Returns `x * unit` nanoseconds, rounded to the nearest nanosecond with
+	/// ties to even like [StdDuration::from_secs_f64].
+	///
+	/// The product is computed exactly, so `x` isn't rounded twice.
+	const fn checked_from_f64(x: f64, unit: u128) -> Option<Self> {
+		if !x.is_finite() || x < 0.0 {
+			return None;
+		}
+
+		// `x` is `mant * 2^exp`.
+		let bits = x.to_bits();
+		let biased_exp = (bits >> 52) as i32 & 0x7ff;
+		let mant = bits & ((1 << 52) - 1);
+		let (mant, exp) = if biased_exp == 0 {
+			(mant, -1074)
+		} else {
+			(mant | 1 << 52, biased_exp - 1075)
+		};
+
+		// At most 2^53 * 10^9, well below 2^127.
+		let n = mant as u128 * unit;
+		if n == 0 {
+			Some(Self(0))
+		} else if exp >= 0 {
+			if exp as u32 > n.leading_zeros() {
+				None
+			} else {
+				Some(Self(n << exp))
+			}
+		} else if exp <= -128 {
+			Some(Self(0))
+		} else {
+			let shift = -exp as u32;
+			let whole = n >> shift;
+			let rem = n & ((1 << shift) - 1);
+			let half = 1 << (shift - 1);
+			if rem > half || (rem == half && whole & 1 == 1) {
+				Some(Self(whole + 1))
+			} else {
+				Some(Self(whole))
+			}
+		}
+	}
+
+	/// Creates a new `Duration` from the specified number of .NET ticks, which
+	/// are 100 nanoseconds.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_dotnet_ticks(ticks: u128) -> Option<Self> {
+		Self::checked_from(ticks, TICK)
+	}
+
+	/// Creates a new `Duration` from the specified number of microseconds.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_micros(us: u128) -> Option<Self> {
+		Self::checked_from(us, MICROSECOND)
+	}
+
+	/// Creates a new `Duration` from the specified number of milliseconds.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_millis(ms: u128) -> Option<Self> {
+		Self::checked_from(ms, MILLISECOND)
+	}
+
+	/// Creates a new `Duration` from the specified number of seconds.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::Duration;
+	///
+	/// assert_eq!(Duration::checked_from_secs(5), Some(Duration::from_millis(5000)));
+	/// assert_eq!(Duration::checked_from_secs(u128::MAX), None);
+	/// ```
+	pub const fn checked_from_secs(secs: u128) -> Option<Self> {
+		Self::checked_from(secs, SECOND)
+	}
+
+	/// Creates a new `Duration` from the specified number of minutes.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_mins(mins: u128) -> Option<Self> {
+		Self::checked_from(mins, MINUTE)
+	}
+
+	/// Creates a new `Duration` from the specified number of hours.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_hours(hours: u128) -> Option<Self> {
+		Self::checked_from(hours, HOUR)
+	}
+
+	/// Creates a new `Duration` from the specified number of days.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_days(days: u128) -> Option<Self> {
+		Self::checked_from(days, DAY)
+	}
+
+	/// Creates a new `Duration` from the specified number of weeks.
+	///
+	/// Returns [None] if the value in nanoseconds overflows a [u128].
+	pub const fn checked_from_weeks(weeks: u128) -> Option<Self> {
+		Self::checked_from(weeks, WEEK)
+	}
+
+	/// Creates a new `Duration` from the specified number of seconds
+	/// represented as an [f64].
+	///
+	/// #### Panics
+	/// Panics if `secs` is negative, not finite or overflows a `Duration`.
+	pub const fn from_secs_f64(secs: f64) -> Self {
+		Self::try_from_secs_f64(secs)
+			.expect("the value is negative, not finite or too big for a Duration")
+	}
+
+	/// Creates a new `Duration` from the specified number of seconds
+	/// represented as an [f64].
+	///
+	/// Returns [None] if `secs` is negative, not finite or overflows a
+	/// `Duration`.
+	pub const fn try_from_secs_f64(secs: f64) -> Option<Self> {
+		Self::checked_from_f64(secs, SECOND)
+	}
+
+	/// Creates a new `Duration` from the specified number of seconds
+	/// represented as an [f32].
+	///
+	/// #### Panics
+	/// Panics if `secs` is negative, not finite or overflows a `Duration`.
+	pub const fn from_secs_f32(secs: f32) -> Self {
+		Self::from_secs_f64(secs as f64)
+	}
+
+	/// Creates a new `Duration` from the specified number of seconds
+	/// represented as an [f32].
+	///
+	/// Returns [None] if `secs` is negative, not finite or overflows a
+	/// `Duration`.
+	pub const fn try_from_secs_f32(secs: f32) -> Option<Self> {
+		Self::try_from_secs_f64(secs as f64)
+	}
+
 	/// Convert to [StdDuration]. equivalent to calling [Into::into].
 	///
 	/// #### Panics
@@ -413,6 +544,12 @@ impl Duration {
 		self.0
 	}
 
+	/// Returns the total number of whole .NET ticks, which are 100
+	/// nanoseconds, contained by this Duration.
+	pub const fn as_dotnet_ticks(self) -> u128 {
+		self.0 / TICK
+	}
+
 	/// Returns the total number of whole microseconds contained by this
 	/// Duration.
 	pub const fn as_micros(self) -> u128 {
@@ -454,15 +591,357 @@ impl Duration {
 		)
 	}
 
+	/// Creates a new `Duration` from a number of `unit`s.
+	///
+	/// Fractions of a nanosecond are truncated.
+	///
+	/// #### Panics
+	/// Panics if `n` is negative or the result doesn't fit in a [u128].
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Decimal, Duration, Unit};
+	///
+	/// let d = Duration::from_unit(Decimal::new(15, 1), Unit::Hour);
+	/// assert_eq!(d, Duration::from_secs(90 * 60));
+	/// ```
+	pub fn from_unit(n: Decimal, unit: Unit) -> Self {
+		Self::checked_from_unit(n, unit).expect("the duration is negative or too big")
+	}
+
+	/// Creates a new `Duration` from a number of `unit`s.
+	///
+	/// Fractions of a nanosecond are truncated.
+	/// Returns [None] if `n` is negative or the result doesn't fit in a
+	/// [u128].
+	pub fn checked_from_unit(n: Decimal, unit: Unit) -> Option<Self> {
+		if n.is_sign_negative() && !n.is_zero() {
+			return None;
+		}
+		Decimal::from(unit.nanos())
+			.checked_mul(n)
+			.and_then(|d| u128::try_from(d.trunc()).ok())
+			.map(Self)
+	}
+
+	/// Returns this duration as a [Decimal] number of `unit`s.
+	///
+	/// Saturates at [Decimal::MAX] if the value is too big.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Decimal, Duration, Unit};
+	///
+	/// let d = Duration::from_secs(90);
+	/// assert_eq!(d.as_unit(Unit::Minute), Decimal::new(15, 1));
+	/// ```
+	pub fn as_unit(self, unit: Unit) -> Decimal {
+		let unit = unit.nanos();
+		to_dec(self.0 / unit)
+			.and_then(|n| n.checked_add(Decimal::from(self.0 % unit) / Decimal::from(unit)))
+			.unwrap_or(Decimal::MAX)
+	}
+
+	/// Returns the number of whole `unit`s contained by this duration.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Unit};
+	///
+	/// let d = Duration::from_secs(100);
+	/// assert_eq!(d.in_whole(Unit::Minute), 1);
+	/// ```
+	pub const fn in_whole(self, unit: Unit) -> u128 {
+		self.0 / unit.nanos()
+	}
+
+	/// Returns the number of seconds contained by this duration as an [f64].
+	pub const fn as_secs_f64(self) -> f64 {
+		self.0 as f64 / SECOND as f64
+	}
+
+	/// Returns the number of seconds contained by this duration as an [f32].
+	pub const fn as_secs_f32(self) -> f32 {
+		self.as_secs_f64() as f32
+	}
+
+	/// Returns the number of milliseconds contained by this duration as an
+	/// [f64].
+	pub const fn as_millis_f64(self) -> f64 {
+		self.0 as f64 / MILLISECOND as f64
+	}
+
+	/// Returns the number of milliseconds contained by this duration as an
+	/// [f32].
+	pub const fn as_millis_f32(self) -> f32 {
+		self.as_millis_f64() as f32
+	}
+
+	/// Returns the total number of whole minutes contained by this Duration.
+	pub const fn as_mins(self) -> u128 {
+		self.0 / MINUTE
+	}
+
+	/// Returns the total number of whole hours contained by this Duration.
+	pub const fn as_hours(self) -> u128 {
+		self.0 / HOUR
+	}
+
+	/// Returns the total number of whole days contained by this Duration.
+	pub const fn as_days(self) -> u128 {
+		self.0 / DAY
+	}
+
+	/// Returns the total number of whole weeks contained by this Duration.
+	pub const fn as_weeks(self) -> u128 {
+		self.0 / WEEK
+	}
+
+	/// Returns the fractional part of this duration, in whole nanoseconds.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::Duration;
+	///
+	/// let d = Duration::from_nanos(5_012_345_678);
+	/// assert_eq!(d.subsec_nanos(), 12_345_678);
+	/// assert_eq!(d.subsec_micros(), 12_345);
+	/// assert_eq!(d.subsec_millis(), 12);
+	/// ```
+	pub const fn subsec_nanos(self) -> u32 {
+		(self.0 % SECOND) as u32
+	}
+
+	/// Returns the fractional part of this duration, in whole microseconds.
+	pub const fn subsec_micros(self) -> u32 {
+		self.subsec_nanos() / MICROSECOND as u32
+	}
+
+	/// Returns the fractional part of this duration, in whole milliseconds.
+	pub const fn subsec_millis(self) -> u32 {
+		self.subsec_nanos() / MILLISECOND as u32
+	}
+
 	/// Returns true if this Duration is 0.
 	pub const fn is_zero(self) -> bool {
 		self.0 == 0
 	}
 
+	/// Checked addition. Returns [None] if the result overflows.
+	pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+		match self.0.checked_add(rhs.0) {
+			Some(n) => Some(Self(n)),
+			None => None,
+		}
+	}
+
+	/// Saturating addition. Returns the maximum representable duration if the
+	/// result overflows.
+	pub const fn saturating_add(self, rhs: Self) -> Self {
+		Self(self.0.saturating_add(rhs.0))
+	}
+
+	/// Checked subtraction. Returns [None] if the result would be negative.
+	pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+		match self.0.checked_sub(rhs.0) {
+			Some(n) => Some(Self(n)),
+			None => None,
+		}
+	}
+
+	/// Saturating subtraction. Returns [Duration::ZERO] if the result would be
+	/// negative.
+	pub const fn saturating_sub(self, rhs: Self) -> Self {
+		Self(self.0.saturating_sub(rhs.0))
+	}
+
+	/// Returns the absolute difference between `self` and `other`.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::Duration;
+	///
+	/// let (a, b) = (Duration::from_secs(3), Duration::from_secs(5));
+	/// assert_eq!(a.abs_diff(b), Duration::from_secs(2));
+	/// assert_eq!(b.abs_diff(a), Duration::from_secs(2));
+	/// ```
+	pub const fn abs_diff(self, other: Self) -> Self {
+		Self(self.0.abs_diff(other.0))
+	}
+
+	/// Checked multiplication. Returns [None] if the result overflows.
+	pub const fn checked_mul(self, rhs: u128) -> Option<Self> {
+		match self.0.checked_mul(rhs) {
+			Some(n) => Some(Self(n)),
+			None => None,
+		}
+	}
+
+	/// Saturating multiplication. Returns [Duration::MAX] if the result
+	/// overflows.
+	pub const fn saturating_mul(self, rhs: u128) -> Self {
+		Self(self.0.saturating_mul(rhs))
+	}
+
+	/// Checked division. Returns [None] if `rhs` is zero.
+	pub const fn checked_div(self, rhs: u128) -> Option<Self> {
+		match self.0.checked_div(rhs) {
+			Some(n) => Some(Self(n)),
+			None => None,
+		}
+	}
+
+	/// Multiplies this duration by an [f64].
+	///
+	/// #### Panics
+	/// Panics if the result is negative, not finite or overflows a `Duration`.
+	pub const fn mul_f64(self, rhs: f64) -> Self {
+		Self::checked_from_f64(self.0 as f64 * rhs, 1)
+			.expect("the value is negative, not finite or too big for a Duration")
+	}
+
+	/// Divides this duration by an [f64].
+	///
+	/// #### Panics
+	/// Panics if the result is negative, not finite or overflows a `Duration`.
+	pub const fn div_f64(self, rhs: f64) -> Self {
+		Self::checked_from_f64(self.0 as f64 / rhs, 1)
+			.expect("the value is negative, not finite or too big for a Duration")
+	}
+
+	/// Divides this duration by another and returns the ratio as an [f64].
+	pub const fn div_duration_f64(self, rhs: Self) -> f64 {
+		self.0 as f64 / rhs.0 as f64
+	}
+
+	/// Sums up the durations in `iter`, returning [None] on overflow.
+	///
+	/// The [Sum](core::iter::Sum) implementations on [Duration] panic on
+	/// overflow instead.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, StdDuration};
+	///
+	/// let v = [Duration::from_secs(1), Duration::from_secs(2)];
+	/// assert_eq!(Duration::checked_sum(v), Some(Duration::from_secs(3)));
+	///
+	/// let v = [StdDuration::from_secs(1), StdDuration::from_secs(2)];
+	/// assert_eq!(Duration::checked_sum(v), Some(Duration::from_secs(3)));
+	///
+	/// let v = [Duration::from_nanos(u128::MAX), Duration::from_nanos(1)];
+	/// assert_eq!(Duration::checked_sum(v), None);
+	/// ```
+	pub fn checked_sum<I>(iter: I) -> Option<Self>
+	where
+		I: IntoIterator,
+		I::Item: Into<Self>,
+	{
+		iter.into_iter()
+			.try_fold(Self(0), |acc, d| acc.checked_add(d.into()))
+	}
+
+	/// Sums up the durations in `iter`, saturating at the maximum representable
+	/// duration instead of overflowing.
+	pub fn saturating_sum<I>(iter: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<Self>,
+	{
+		iter.into_iter()
+			.fold(Self(0), |acc, d| acc.saturating_add(d.into()))
+	}
+
 	/// Returns a struct with a lossless [Display] implementation.
 	pub fn format_exact(self) -> ExactDisplay {
 		ExactDisplay(self.0)
 	}
+
+	/// Returns a struct that displays the duration like Go's
+	/// `time.Duration.String`.
+	///
+	/// See [GoDisplay] for details and [parse_go] for parsing it back.
+	pub fn format_go(self) -> GoDisplay {
+		GoDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration as a systemd time span.
+	///
+	/// See [SystemdDisplay] for details and [parse_systemd] for parsing it
+	/// back.
+	pub fn format_systemd(self) -> SystemdDisplay {
+		SystemdDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration as a PostgreSQL interval.
+	///
+	/// See [PostgresDisplay] for details and [parse_postgres] for parsing it
+	/// back.
+	pub fn format_postgres(self) -> PostgresDisplay {
+		PostgresDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration like Prometheus.
+	///
+	/// See [PrometheusDisplay] for details and [parse_prometheus] for parsing
+	/// it back.
+	pub fn format_prometheus(self) -> PrometheusDisplay {
+		PrometheusDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration like the `humantime` crate.
+	///
+	/// See [HumantimeDisplay] for details and [parse_humantime] for parsing
+	/// it back.
+	pub fn format_humantime(self) -> HumantimeDisplay {
+		HumantimeDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration like .NET's
+	/// `TimeSpan.ToString()`.
+	///
+	/// See [DotnetDisplay] for details and [parse_dotnet] for parsing it back.
+	pub fn format_dotnet(self) -> DotnetDisplay {
+		DotnetDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration in the general short (`"g"`)
+	/// format of .NET's `TimeSpan`.
+	///
+	/// See [DotnetGeneralDisplay] for details and [parse_dotnet] for parsing it
+	/// back.
+	pub fn format_dotnet_general(self) -> DotnetGeneralDisplay {
+		DotnetGeneralDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration like Java's
+	/// `Duration.toString`.
+	///
+	/// See [JavaDisplay] for details and [parse_java] for parsing it back.
+	pub fn format_java(self) -> JavaDisplay {
+		JavaDisplay(self.0)
+	}
+
+	/// Returns a struct that displays the duration in the syntax of a
+	/// [Dialect](dialect::Dialect).
+	///
+	/// See the [dialect] module for details.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{
+	///     dialect::Java,
+	///     Duration,
+	/// };
+	///
+	/// assert_eq!(Duration::from_mins(90).display_as::<Java>().to_string(), "PT1H30M");
+	/// ```
+	pub fn display_as<D: dialect::Dialect>(self) -> dialect::DisplayAs<D> {
+		dialect::DisplayAs {
+			d: self,
+			dialect: core::marker::PhantomData,
+		}
+	}
 }
 
 // Trait impls
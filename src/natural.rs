@@ -0,0 +1,298 @@
+use crate::{
+	number::Number,
+	*,
+};
+
+/// The number words below twenty, indexed by their value.
+const SMALL: [&str; 20] = [
+	"zero",
+	"one",
+	"two",
+	"three",
+	"four",
+	"five",
+	"six",
+	"seven",
+	"eight",
+	"nine",
+	"ten",
+	"eleven",
+	"twelve",
+	"thirteen",
+	"fourteen",
+	"fifteen",
+	"sixteen",
+	"seventeen",
+	"eighteen",
+	"nineteen",
+];
+
+/// The multiples of ten from twenty to ninety.
+const TENS: [&str; 8] = [
+	"twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(&str, u64); 3] = [
+	("thousand", 1_000),
+	("million", 1_000_000),
+	("billion", 1_000_000_000),
+];
+
+#[derive(Copy, Clone)]
+enum Word {
+	Small(u64),
+	Ten(u64),
+	Hundred,
+	Scale(u64),
+}
+
+impl Word {
+	fn parse(w: &str) -> Option<Self> {
+		let find = |words: &[&str]| words.iter().position(|s| s.eq_ignore_ascii_case(w));
+		if let Some(n) = find(&SMALL) {
+			Some(Self::Small(n as u64))
+		} else if let Some(n) = find(&TENS) {
+			Some(Self::Ten(20 + 10 * n as u64))
+		} else if w.eq_ignore_ascii_case("hundred") {
+			Some(Self::Hundred)
+		} else {
+			SCALES
+				.iter()
+				.find(|(s, _)| s.eq_ignore_ascii_case(w))
+				.map(|&(_, n)| Self::Scale(n))
+		}
+	}
+}
+
+/// Splits the word at the start of `s` off, skipping leading whitespace.
+fn word(s: &str) -> Option<(&str, &str)> {
+	let s = s.trim_start();
+	let len = s.find(|c: char| !c.is_alphabetic()).unwrap_or(s.len());
+	(len != 0).then(|| s.split_at(len))
+}
+
+/// Consumes `w` if it's the word at the start of `s`, ignoring case.
+fn keyword<'a>(s: &'a str, w: &str) -> Option<&'a str> {
+	word(s)
+		.filter(|(x, _)| x.eq_ignore_ascii_case(w))
+		.map(|(_, rem)| rem)
+}
+
+/// Consumes an optional `of` followed by an optional article, as in
+/// `quarter of an hour`.
+fn of_article(s: &str) -> &str {
+	let s = keyword(s, "of").unwrap_or(s);
+	keyword(s, "a").or_else(|| keyword(s, "an")).unwrap_or(s)
+}
+
+/// Parses `half` or `quarter`.
+fn fraction(s: &str) -> Option<(Decimal, &str)> {
+	let (w, rem) = word(s)?;
+	if w.eq_ignore_ascii_case("half") {
+		Some((Decimal::new(5, 1), rem))
+	} else if w.eq_ignore_ascii_case("quarter") {
+		Some((Decimal::new(25, 2), rem))
+	} else {
+		None
+	}
+}
+
+/// Parses `and a half` or `and a quarter`.
+fn and_a_fraction(s: &str) -> Option<(Decimal, &str)> {
+	let s = keyword(s, "and")?;
+	fraction(keyword(s, "a")?)
+}
+
+/// Parses a number spelled out in words, such as `one hundred and twenty-five`.
+///
+/// If `article` is true, the number follows `a` or `an`, which stands for
+/// `one` before `hundred` or a scale word, as in `a thousand`.
+fn number_words(input: &str, article: bool) -> Option<(u64, &str)> {
+	let mut s = input;
+	let (mut total, mut current) = (0, article as u64);
+	let mut last = article.then_some(Word::Small(1));
+	let mut last_scale = u64::MAX;
+
+	loop {
+		let mut next = s;
+		match last {
+			// `and` is part of the number only after `hundred` or a scale
+			// word.
+			Some(Word::Hundred | Word::Scale(_)) => next = keyword(next, "and").unwrap_or(next),
+			// `twenty-one`
+			Some(Word::Ten(_)) => next = next.strip_prefix('-').unwrap_or(next),
+			_ => (),
+		}
+		let Some((w, rem)) = word(next) else { break };
+		let Some(word) = Word::parse(w) else { break };
+
+		let valid = match (last, word) {
+			(None, Word::Small(_) | Word::Ten(_)) => true,
+			(Some(Word::Small(n)), Word::Hundred) => n != 0 && current < 100,
+			(Some(Word::Small(n)), Word::Scale(_)) => n != 0,
+			(Some(Word::Ten(_)), Word::Small(n)) => (1..10).contains(&n),
+			(Some(Word::Ten(_) | Word::Hundred), Word::Scale(_)) => true,
+			(Some(Word::Hundred | Word::Scale(_)), Word::Small(n)) => n != 0,
+			(Some(Word::Hundred | Word::Scale(_)), Word::Ten(_)) => true,
+			_ => false,
+		};
+		match word {
+			_ if !valid => break,
+			Word::Small(n) | Word::Ten(n) => current += n,
+			Word::Hundred => current *= 100,
+			Word::Scale(n) if n < last_scale => {
+				total += current * n;
+				current = 0;
+				last_scale = n;
+			}
+			Word::Scale(_) => break,
+		}
+		last = Some(word);
+		s = rem;
+	}
+
+	(s.len() != input.len()).then_some((total + current, s))
+}
+
+/// Parses an amount spelled out in words, such as `twenty-one`, `an`,
+/// `half a` or `a couple of`.
+fn spelled(s: &str) -> Option<(Decimal, &str)> {
+	let (w, rem) = word(s)?;
+	if w.eq_ignore_ascii_case("a") || w.eq_ignore_ascii_case("an") {
+		let n = number_words(rem, true)
+			.map(|(n, rem)| (Decimal::from(n), rem))
+			.or_else(|| fraction(rem).map(|(f, rem)| (f, of_article(rem))))
+			.or_else(|| keyword(rem, "couple").map(|rem| (Decimal::TWO, of_article(rem))))
+			.unwrap_or((Decimal::ONE, rem));
+		return Some(n);
+	}
+	if w.eq_ignore_ascii_case("couple") {
+		return Some((Decimal::TWO, of_article(rem)));
+	}
+	if let Some((f, rem)) = fraction(s) {
+		return Some((f, of_article(rem)));
+	}
+	number_words(s, false).map(|(n, rem)| (Decimal::from(n), rem))
+}
+
+/// Parses an amount and a unit, returning the duration in nanoseconds, the
+/// discarded fraction of a nanosecond and the rest of the input.
+///
+/// `s` must be a suffix of `input`, which the error spans are relative to.
+fn component<'a>(
+	parser: &Parser,
+	input: &str,
+	s: &'a str,
+) -> Result<(u128, Decimal, &'a str), Error> {
+	let pos = |s: &str| input.len() - s.len();
+	let start = pos(s);
+	let is_unit = |s: &str| parser.parse_unit(s.trim_start()).is_ok();
+
+	let (mut n, mut rem) = match spelled(s) {
+		Some((value, rem)) => (
+			Number {
+				value,
+				denominator: 1,
+			},
+			rem,
+		),
+		None => parser
+			.parse_number(s)
+			.map(|(rem, n)| (n, rem))
+			.map_err(|e| e.offset(start))?,
+	};
+	if n.value.is_sign_negative() {
+		return Err(Error::new(
+			ErrorKind::IsNegative(n.to_decimal()),
+			start..pos(rem),
+		));
+	}
+	// The `and a half` parts.
+	let mut fraction = Decimal::ZERO;
+
+	// `two and a half days`, `three quarters of an hour`
+	if let Some((f, r)) = and_a_fraction(rem).filter(|&(_, r)| is_unit(r)) {
+		fraction = f;
+		rem = r;
+	} else if let Some((w, r)) = word(rem).filter(|_| !is_unit(rem)) {
+		if w.eq_ignore_ascii_case("quarter") || w.eq_ignore_ascii_case("quarters") {
+			n.value *= Decimal::new(25, 2);
+			rem = of_article(r);
+		}
+	}
+
+	let number = start..pos(rem);
+	let rem = rem.trim_start();
+	let (mut rem, unit) = parser.parse_unit(rem).map_err(|e| match e.kind() {
+		// Point at the amount without a unit.
+		ErrorKind::MissingUnit => Error::new(ErrorKind::MissingUnit, number.clone()),
+		_ => e.offset(pos(rem)),
+	})?;
+
+	// `an hour and a half`, unless it's `an hour and a half minute`.
+	if let Some((f, r)) = and_a_fraction(rem).filter(|&(_, r)| !is_unit(r)) {
+		fraction += f;
+		rem = r;
+	}
+
+	let too_big = Error::new(ErrorKind::ValueTooBig, start..pos(rem));
+	// `value / denominator + fraction`
+	n.value = to_dec(n.denominator)
+		.and_then(|d| d.checked_mul(fraction))
+		.and_then(|f| n.value.checked_add(f))
+		.ok_or(too_big.clone())?;
+	parser
+		.nanos(n, unit)
+		.map(|(ns, remainder)| (ns, remainder, rem))
+		.map_err(|kind| Error::new(kind, too_big.span()))
+}
+
+/// Parses `input` in the natural-language mode of `parser`, returning the
+/// duration, the remainder and the rest of the input.
+///
+/// If `prefix` is true, parsing stops before the first part after the first
+/// that isn't an amount followed by a unit; otherwise the whole input must be
+/// a duration.
+///
+/// See [Parser::with_natural_language].
+pub(crate) fn parse<'a>(
+	parser: &Parser,
+	input: &'a str,
+	prefix: bool,
+) -> Result<(Duration, Decimal, &'a str), Error> {
+	let pos = |s: &str| input.len() - s.len();
+	let mut s = input.trim_start();
+	let mut n = 0_u128;
+	let mut remainder = Decimal::ZERO;
+	let mut before = s;
+
+	for i in 0.. {
+		let start = pos(s);
+		let (d, r, rem) = match component(parser, input, s) {
+			Ok(x) => x,
+			Err(e) if prefix && i != 0 && parser::is_not_a_part(e.kind()) => break,
+			Err(e) => return Err(e),
+		};
+		n = n
+			.checked_add(d)
+			.ok_or(Error::new(ErrorKind::ValueTooBig, start..pos(rem)))?;
+		remainder += r;
+		before = rem;
+
+		// Parts are separated by whitespace, a comma and/or `and`.
+		s = rem.trim_start();
+		if let Some(rem) = s.strip_prefix(',') {
+			s = rem.trim_start();
+		}
+		// A trailing `and` is an error.
+		if let Some(rem) = keyword(s, "and").filter(|rem| prefix || !rem.trim().is_empty()) {
+			s = rem.trim_start();
+		}
+		if s.is_empty() {
+			before = s;
+			break;
+		}
+	}
+
+	Ok((Duration(n), remainder, before))
+}
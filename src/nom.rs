@@ -0,0 +1,142 @@
+//! Combinators for embedding durations in larger grammars written with
+//! [nom](https://docs.rs/nom/7) 7.
+//!
+//! The combinators are generic over the nom error type. Invalid input becomes
+//! a recoverable [Err::Error] pointing at the offending part of the input;
+//! the underlying [Error] is handed to [FromExternalError], so error types
+//! such as `nom::error::Error` and `nom::error::VerboseError` just work.
+//!
+//! Like most nom parsers, the combinators don't skip leading whitespace.
+//!
+//! #### Examples
+//! ```rust
+//! use dur::Duration;
+//! use nom::{
+//!     bytes::complete::tag,
+//!     character::complete::digit1,
+//!     sequence::{
+//!         delimited,
+//!         separated_pair,
+//!     },
+//!     IResult,
+//! };
+//!
+//! /// Parses `retry(times, delay)`.
+//! fn retry(input: &str) -> IResult<&str, (&str, Duration)> {
+//!     delimited(
+//!         tag("retry("),
+//!         separated_pair(digit1, tag(", "), dur::nom::duration),
+//!         tag(")"),
+//!     )(input)
+//! }
+//!
+//! assert_eq!(
+//!     retry("retry(3, 5s) then wait 1m"),
+//!     Ok((" then wait 1m", ("3", Duration::from_secs(5))))
+//! );
+//! assert!(retry("retry(3, 5 parsecs)").is_err());
+//! ```
+
+use ::nom::{
+	error::{
+		ErrorKind as NomErrorKind,
+		FromExternalError,
+		ParseError,
+	},
+	Err,
+	IResult,
+};
+
+use crate::{
+	number,
+	unit::match_unit,
+	Decimal,
+	Duration,
+	Error,
+	ErrorKind,
+	Unit,
+};
+
+/// Converts `e`, an error while parsing `input`, to a nom error.
+fn error<'a, E>(input: &'a str, e: Error) -> Err<E>
+where
+	E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+	let kind = match e.kind() {
+		ErrorKind::InvalidDuration => NomErrorKind::Digit,
+		ErrorKind::MissingUnit | ErrorKind::InvalidUnit => NomErrorKind::Tag,
+		_ => NomErrorKind::Verify,
+	};
+	Err::Error(E::from_external_error(&input[e.span().start..], kind, e))
+}
+
+/// Parses a duration at the start of the input.
+///
+/// See [parse_prefix](crate::parse_prefix) for where the duration ends.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(
+///     dur::nom::duration::<nom::error::Error<_>>("1m 30s later"),
+///     Ok((" later", Duration::from_secs(90)))
+/// );
+/// ```
+pub fn duration<'a, E>(input: &'a str) -> IResult<&'a str, Duration, E>
+where
+	E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+	crate::parse_prefix(input)
+		.map(|(d, rem)| (rem, d))
+		.map_err(|e| error(input, e))
+}
+
+/// Parses a built-in unit at the start of the input, such as `ms` or
+/// `minutes`.
+///
+/// The unit can't be followed by a letter.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Unit;
+///
+/// assert_eq!(
+///     dur::nom::unit::<nom::error::Error<_>>("secs)"),
+///     Ok((")", Unit::Second))
+/// );
+/// assert!(dur::nom::unit::<nom::error::Error<_>>("parsecs").is_err());
+/// ```
+pub fn unit<'a, E>(input: &'a str) -> IResult<&'a str, Unit, E>
+where
+	E: ParseError<&'a str>,
+{
+	match match_unit(input.as_bytes()) {
+		Some((unit, len)) if !input[len..].starts_with(|c: char| c.is_alphabetic()) => {
+			Ok((&input[len..], unit))
+		}
+		_ => Err(Err::Error(E::from_error_kind(input, NomErrorKind::Tag))),
+	}
+}
+
+/// Parses a number at the start of the input, such as `1`, `-1.5` or `.5`.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Decimal;
+///
+/// assert_eq!(
+///     dur::nom::number::<nom::error::Error<_>>("1.5h"),
+///     Ok(("h", Decimal::new(15, 1)))
+/// );
+/// ```
+pub fn number<'a, E>(input: &'a str) -> IResult<&'a str, Decimal, E>
+where
+	E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+	match number::number(input, Default::default()) {
+		Ok(Some((rem, n))) => Ok((rem, n.value)),
+		Ok(None) => Err(Err::Error(E::from_error_kind(input, NomErrorKind::Digit))),
+		Err(e) => Err(error(input, e)),
+	}
+}
@@ -0,0 +1,225 @@
+use crate::*;
+
+/// The optional extensions of the number syntax, see [Parser].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct Syntax {
+	pub(crate) exponents: bool,
+	pub(crate) separators: bool,
+	pub(crate) rationals: bool,
+	pub(crate) vulgar_fractions: bool,
+}
+
+impl Syntax {
+	pub(crate) const fn any(self) -> bool {
+		self.exponents || self.separators || self.rationals || self.vulgar_fractions
+	}
+}
+
+/// The Unicode vulgar fractions as `(numerator, denominator)` pairs.
+const VULGAR_FRACTIONS: [(char, u128, u128); 18] = [
+	('½', 1, 2),
+	('⅓', 1, 3),
+	('⅔', 2, 3),
+	('¼', 1, 4),
+	('¾', 3, 4),
+	('⅕', 1, 5),
+	('⅖', 2, 5),
+	('⅗', 3, 5),
+	('⅘', 4, 5),
+	('⅙', 1, 6),
+	('⅚', 5, 6),
+	('⅐', 1, 7),
+	('⅛', 1, 8),
+	('⅜', 3, 8),
+	('⅝', 5, 8),
+	('⅞', 7, 8),
+	('⅑', 1, 9),
+	('⅒', 1, 10),
+];
+
+/// The largest magnitude of an exponent, the largest scale of a [Decimal].
+const MAX_EXPONENT: u32 = 28;
+/// The largest integer a [Decimal] can hold.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+/// A number as `value / denominator`, which keeps rationals exact.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Number {
+	pub(crate) value: Decimal,
+	pub(crate) denominator: u128,
+}
+
+impl Number {
+	/// Returns the value as a single [Decimal], rounded if it isn't exact.
+	pub(crate) fn to_decimal(self) -> Decimal {
+		// A denominator that big only comes with tiny values.
+		let d = to_dec(self.denominator).unwrap_or(Decimal::MAX);
+		self.value.checked_div(d).unwrap_or(self.value)
+	}
+}
+
+fn vulgar_fraction(s: &str) -> Option<(u128, u128, usize)> {
+	let c = s.chars().next()?;
+	VULGAR_FRACTIONS
+		.iter()
+		.find(|&&(f, ..)| f == c)
+		.map(|&(_, n, d)| (n, d, c.len_utf8()))
+}
+
+/// Returns the number of leading ASCII digits in `s`, also counting `_` after
+/// the first digit if `underscores` is true.
+fn digits(s: &[u8], underscores: bool) -> usize {
+	match s.first() {
+		Some(b) if b.is_ascii_digit() => s
+			.iter()
+			.position(|&b| !(b.is_ascii_digit() || (underscores && b == b'_')))
+			.unwrap_or(s.len()),
+		_ => 0,
+	}
+}
+
+/// Parses an integer with digit separators, or returns [None] if it's bigger
+/// than the largest integer a [Decimal] can hold.
+fn integer(s: &str) -> Option<u128> {
+	let mut n = 0_u128;
+	for b in s.bytes().filter(u8::is_ascii_digit) {
+		n = n * 10 + u128::from(b - b'0');
+		if n > MAX_MANTISSA {
+			return None;
+		}
+	}
+	Some(n)
+}
+
+/// Parses a signed number at the start of `s`.
+///
+/// Returns the rest of the input and the number, or [None] if `s` doesn't
+/// start with a number.
+/// The span of the error is relative to `s`.
+pub(crate) fn number(s: &str, syntax: Syntax) -> Result<Option<(&str, Number)>, Error> {
+	let b = s.as_bytes();
+	let underscores = syntax.separators;
+
+	let sign = usize::from(matches!(b.first(), Some(b'-' | b'+')));
+	let mut i = sign + digits(&b[sign..], underscores);
+	let mut grouped = false;
+
+	// `1,000,000`
+	while syntax.separators && i > sign && b.get(i) == Some(&b',') {
+		let group = &b[i + 1..];
+		if group.len() < 3
+			|| !group[..3].iter().all(u8::is_ascii_digit)
+			|| group.get(3).is_some_and(u8::is_ascii_digit)
+		{
+			break;
+		}
+		grouped = true;
+		i += 4;
+	}
+	let int_end = i;
+
+	let mut point = None;
+	if b.get(i) == Some(&b'.') {
+		let n = digits(&b[i + 1..], underscores);
+		// `1.` and `.5` are numbers but `.` isn't.
+		if n != 0 || i > sign {
+			point = Some(i);
+			i += 1 + n;
+		}
+	}
+
+	let vulgar = match (syntax.vulgar_fractions, point) {
+		(true, None) => vulgar_fraction(&s[i..]),
+		_ => None,
+	};
+	if i == sign && vulgar.is_none() {
+		return Ok(None);
+	}
+
+	let negative = b.first() == Some(&b'-');
+	let mantissa = &s[..i];
+	let mut value = if grouped {
+		let frac = match point {
+			Some(p) => parse_dec(&s[p..i]).map_err(|kind| Error::new(kind, 0..i))?,
+			None => Decimal::ZERO,
+		};
+		let n = integer(&s[sign..int_end])
+			.and_then(|n| Decimal::from(n).checked_add(frac))
+			.ok_or(Error::new(ErrorKind::ValueTooBig, 0..i))?;
+		if negative {
+			-n
+		} else {
+			n
+		}
+	} else if i == sign {
+		// A lone vulgar fraction, such as `½`.
+		Decimal::ZERO
+	} else {
+		parse_dec(mantissa).map_err(|kind| Error::new(kind, 0..i))?
+	};
+	let mut denominator = 1;
+
+	if let Some((n, d, len)) = vulgar {
+		i += len;
+		let n = Decimal::from(n);
+		value = value
+			.checked_mul(Decimal::from(d))
+			.and_then(|v| {
+				if negative {
+					v.checked_sub(n)
+				} else {
+					v.checked_add(n)
+				}
+			})
+			.ok_or(Error::new(ErrorKind::ValueTooBig, 0..i))?;
+		denominator = d;
+	}
+
+	// `1e3`, `1.5E-3`
+	if syntax.exponents && matches!(b.get(i), Some(b'e' | b'E')) {
+		let j = i + 1;
+		let exp_sign = usize::from(matches!(b.get(j), Some(b'-' | b'+')));
+		let n = digits(&b[j + exp_sign..], false);
+		if n != 0 {
+			let end = j + exp_sign + n;
+			let span = 0..end;
+			let exp = s[j + exp_sign..end]
+				.parse::<u32>()
+				.ok()
+				.filter(|&e| e <= MAX_EXPONENT)
+				.ok_or(Error::new(ErrorKind::InvalidDuration, span.clone()))?;
+			let pow = 10_u128.pow(exp);
+			if b[j] == b'-' {
+				// Dividing keeps the value exact.
+				denominator = denominator
+					.checked_mul(pow)
+					.ok_or(Error::new(ErrorKind::ValueTooBig, span))?;
+			} else {
+				value = value
+					.checked_mul(Decimal::from(pow))
+					.ok_or(Error::new(ErrorKind::ValueTooBig, span))?;
+			}
+			i = end;
+		}
+	}
+
+	// `1/2`
+	if syntax.rationals && b.get(i) == Some(&b'/') {
+		let n = digits(&b[i + 1..], underscores);
+		if n != 0 {
+			let end = i + 1 + n;
+			let span = 0..end;
+			let d =
+				integer(&s[i + 1..end]).ok_or(Error::new(ErrorKind::ValueTooBig, span.clone()))?;
+			if d == 0 {
+				return Err(Error::new(ErrorKind::DivisionByZero, span));
+			}
+			denominator = denominator
+				.checked_mul(d)
+				.ok_or(Error::new(ErrorKind::ValueTooBig, span))?;
+			i = end;
+		}
+	}
+
+	Ok(Some((&s[i..], Number { value, denominator })))
+}
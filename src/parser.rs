@@ -0,0 +1,863 @@
+use core::cmp::Ordering;
+
+use crate::{
+	number::{
+		self,
+		Number,
+	},
+	unit::{
+		match_unit,
+		starts_with_ignore_case,
+	},
+	*,
+};
+
+/// A user-defined unit of time, registered with [Parser::with_units].
+///
+/// A custom unit is recognized by its name, its name followed by an `s` (the
+/// plural) and any of its aliases, all case-insensitively.
+///
+/// #### Examples
+/// ```rust
+/// use dur::CustomUnit;
+///
+/// const TICK: CustomUnit = CustomUnit::new("tick", &["t"], 50_000_000);
+/// // 1/60 of a second.
+/// const FRAME: CustomUnit = CustomUnit::from_ratio("frame", &["f"], 1_000_000_000, 60);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CustomUnit {
+	name: &'static str,
+	aliases: &'static [&'static str],
+	numerator: u128,
+	denominator: u128,
+}
+
+/// Returns true if the name can be matched by the parser: it must be non-empty
+/// and consist of letters only.
+const fn is_valid_name(name: &str) -> bool {
+	let s = name.as_bytes();
+	let mut i = 0;
+	while i < s.len() {
+		if !s[i].is_ascii_alphabetic() && s[i].is_ascii() {
+			return false;
+		}
+		i += 1;
+	}
+	!s.is_empty()
+}
+
+/// Compares `a` and `b`, each optionally followed by an `s`, ignoring ASCII
+/// case.
+const fn forms_eq(a: &[u8], a_plural: bool, b: &[u8], b_plural: bool) -> bool {
+	let len = a.len() + a_plural as usize;
+	if len != b.len() + b_plural as usize {
+		return false;
+	}
+
+	let mut i = 0;
+	while i < len {
+		let x = if i < a.len() { a[i] } else { b's' };
+		let y = if i < b.len() { b[i] } else { b's' };
+		if !x.eq_ignore_ascii_case(&y) {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+impl CustomUnit {
+	/// Creates a unit that is `nanos` nanoseconds long.
+	///
+	/// #### Panics
+	/// Panics if `nanos` is zero or bigger than [Decimal::MAX], or if the name
+	/// or any of the aliases is empty or contains anything other than letters.
+	pub const fn new(name: &'static str, aliases: &'static [&'static str], nanos: u128) -> Self {
+		Self::from_ratio(name, aliases, nanos, 1)
+	}
+
+	/// Creates a unit that is `numerator / denominator` nanoseconds long.
+	///
+	/// #### Panics
+	/// Panics if the numerator or the denominator is zero or bigger than
+	/// [Decimal::MAX], if their product overflows a [u128], or if the name or
+	/// any of the aliases is empty or contains anything other than letters.
+	pub const fn from_ratio(
+		name: &'static str,
+		aliases: &'static [&'static str],
+		numerator: u128,
+		denominator: u128,
+	) -> Self {
+		const DECIMAL_MAX: u128 = (1 << 96) - 1;
+		assert!(
+			numerator != 0 && denominator != 0,
+			"the length of a unit cannot be zero"
+		);
+		assert!(
+			numerator <= DECIMAL_MAX
+				&& denominator <= DECIMAL_MAX
+				&& numerator.checked_mul(denominator).is_some(),
+			"the length of a unit is too big"
+		);
+		assert!(
+			is_valid_name(name),
+			"unit names must be non-empty and only contain letters"
+		);
+		let mut i = 0;
+		while i < aliases.len() {
+			assert!(
+				is_valid_name(aliases[i]),
+				"unit names must be non-empty and only contain letters"
+			);
+			i += 1;
+		}
+
+		Self {
+			name,
+			aliases,
+			numerator,
+			denominator,
+		}
+	}
+
+	/// Returns the name of this unit.
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// Returns the aliases of this unit, not including its name.
+	pub const fn aliases(&self) -> &'static [&'static str] {
+		self.aliases
+	}
+
+	/// Returns the numerator of the length of this unit in nanoseconds.
+	pub const fn numerator(&self) -> u128 {
+		self.numerator
+	}
+
+	/// Returns the denominator of the length of this unit in nanoseconds.
+	pub const fn denominator(&self) -> u128 {
+		self.denominator
+	}
+
+	/// Returns true if any spelling of this unit is also a spelling of `form`
+	/// (optionally followed by an `s`).
+	const fn collides_with(&self, form: &[u8], plural: bool) -> bool {
+		let name = self.name.as_bytes();
+		if forms_eq(name, false, form, plural) || forms_eq(name, true, form, plural) {
+			return true;
+		}
+		let mut i = 0;
+		while i < self.aliases.len() {
+			if forms_eq(self.aliases[i].as_bytes(), false, form, plural) {
+				return true;
+			}
+			i += 1;
+		}
+		false
+	}
+
+	/// Returns true if any spelling of `self` is also a spelling of `other`.
+	const fn collides(&self, other: &Self) -> bool {
+		if other.collides_with(self.name.as_bytes(), false)
+			|| other.collides_with(self.name.as_bytes(), true)
+		{
+			return true;
+		}
+		let mut i = 0;
+		while i < self.aliases.len() {
+			if other.collides_with(self.aliases[i].as_bytes(), false) {
+				return true;
+			}
+			i += 1;
+		}
+		false
+	}
+
+	/// Returns the length of the longest spelling of this unit that `s` starts
+	/// with, if any.
+	fn match_len(&self, s: &[u8]) -> Option<usize> {
+		let name = self.name.as_bytes();
+		let mut best = None;
+		if starts_with_ignore_case(s, name) {
+			best = Some(name.len());
+			if s.get(name.len())
+				.is_some_and(|c| c.eq_ignore_ascii_case(&b's'))
+			{
+				best = Some(name.len() + 1);
+			}
+		}
+
+		for alias in self.aliases {
+			let alias = alias.as_bytes();
+			if starts_with_ignore_case(s, alias) && best.is_none_or(|n| alias.len() > n) {
+				best = Some(alias.len());
+			}
+		}
+
+		best
+	}
+
+	/// Returns the largest `n` such that `n` of this unit, truncated to whole
+	/// nanoseconds like the parser does, is at most `ns`.
+	fn count(&self, ns: u128) -> u128 {
+		let (q, r) = (ns / self.numerator, ns % self.numerator);
+		// `(r + 1) * denominator <= numerator * denominator`, checked on
+		// construction.
+		q.saturating_mul(self.denominator)
+			.saturating_add(((r + 1) * self.denominator - 1) / self.numerator)
+	}
+
+	/// Returns the length of `n` of this unit, truncated to whole nanoseconds.
+	fn length(&self, n: u128) -> Option<u128> {
+		let (q, r) = (n / self.denominator, n % self.denominator);
+		q.checked_mul(self.numerator)?
+			.checked_add(r * self.numerator / self.denominator)
+	}
+}
+
+/// A duration parser, optionally extended with [CustomUnit]s.
+///
+/// [Parser::new] accepts exactly what [parse] accepts; [parse] is a shorthand
+/// for it.
+/// The custom units are stored in a `&'static` slice so that a parser can be
+/// built without allocating and in `const` contexts, where unit names that
+/// collide with the built-in units or with each other are a compile error.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{CustomUnit, Duration, Parser};
+///
+/// const PARSER: Parser = Parser::with_units(&[
+///     CustomUnit::new("tick", &[], 50_000_000),
+///     CustomUnit::from_ratio("frame", &["fr"], 1_000_000_000, 60),
+///     CustomUnit::new("sprint", &[], 2 * Duration::WEEK.as_nanos()),
+///     CustomUnit::new("shift", &[], 8 * Duration::HOUR.as_nanos()),
+/// ]);
+///
+/// assert_eq!(PARSER.parse("2 ticks"), Ok(Duration::from_millis(100)));
+/// assert_eq!(PARSER.parse("30fr"), Ok(Duration::from_millis(500)));
+/// assert_eq!(PARSER.parse("1 sprint 1d"), Ok(Duration::from_days(15)));
+///
+/// // Custom units can also be used for formatting.
+/// let d = PARSER.parse("1 shift 2h").unwrap();
+/// assert_eq!(PARSER.display(d).to_string(), "1 shift 2h");
+/// assert_eq!(d.to_string(), "10h");
+/// ```
+///
+/// ```compile_fail
+/// // `ms` is already a built-in unit.
+/// const PARSER: dur::Parser = dur::Parser::with_units(&[
+///     dur::CustomUnit::new("moonsecond", &["ms"], 42),
+/// ]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Parser {
+	units: &'static [CustomUnit],
+	natural: bool,
+	numbers: number::Syntax,
+	precision: Precision,
+}
+
+impl Parser {
+	/// Creates a parser that only knows the built-in units.
+	pub const fn new() -> Self {
+		Self {
+			units: &[],
+			natural: false,
+			numbers: number::Syntax {
+				exponents: false,
+				separators: false,
+				rationals: false,
+				vulgar_fractions: false,
+			},
+			precision: Precision::Truncate,
+		}
+	}
+
+	/// Creates a parser that knows the built-in units and `units`.
+	///
+	/// #### Panics
+	/// Panics if a spelling of a custom unit is also a spelling of a built-in
+	/// unit or of another custom unit.
+	/// Use this in a `const` to turn that into a compile error.
+	pub const fn with_units(units: &'static [CustomUnit]) -> Self {
+		let mut i = 0;
+		while i < units.len() {
+			let mut j = 0;
+			while j < Unit::ALL.len() {
+				let aliases = Unit::ALL[j].aliases();
+				let mut k = 0;
+				while k < aliases.len() {
+					assert!(
+						!units[i].collides_with(aliases[k].as_bytes(), false),
+						"a custom unit collides with a built-in unit"
+					);
+					k += 1;
+				}
+				j += 1;
+			}
+
+			let mut j = i + 1;
+			while j < units.len() {
+				assert!(
+					!units[i].collides(&units[j]),
+					"two custom units have the same name or alias"
+				);
+				j += 1;
+			}
+			i += 1;
+		}
+
+		Self {
+			units,
+			..Self::new()
+		}
+	}
+
+	/// Enables or disables the natural-language mode.
+	///
+	/// In this mode, the parser also accepts English phrases such as
+	/// `an hour and a half`, `two minutes`, `a couple of seconds`,
+	/// `half an hour` or `1 hour and 30 minutes`:
+	/// - Amounts can be spelled out, from `zero` to the billions, e.g.
+	///   `one hundred and twenty-five`.
+	/// - `a` or `an` stands for one, `a couple of` for two.
+	/// - `half` and `quarter` can precede a unit (`half an hour`,
+	///   `three quarters of an hour`), and `and a half` or `and a quarter` can
+	///   follow an amount or a unit (`two and a half days`, `an hour and a
+	///   half`).
+	/// - Parts can be joined with `and`, besides commas and whitespace.
+	///
+	/// Everything [parse] accepts is still accepted, with the same result.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Parser};
+	///
+	/// const PARSER: Parser = Parser::new().with_natural_language(true);
+	///
+	/// assert_eq!(PARSER.parse("an hour and a half"), Ok(Duration::from_mins(90)));
+	/// assert_eq!(PARSER.parse("half an hour"), Ok(Duration::from_mins(30)));
+	/// assert_eq!(PARSER.parse("a couple of seconds"), Ok(Duration::from_secs(2)));
+	/// assert_eq!(
+	///     PARSER.parse("twenty-one days and 3 hours"),
+	///     Ok(Duration::from_hours(21 * 24 + 3))
+	/// );
+	///
+	/// let e = PARSER.parse("two parsecs").unwrap_err();
+	/// assert_eq!(e.span(), 4..11);
+	/// ```
+	pub const fn with_natural_language(mut self, enabled: bool) -> Self {
+		self.natural = enabled;
+		self
+	}
+
+	/// Enables or disables exponents in numbers, such as `1e3ms` or
+	/// `2.5E-3s`.
+	///
+	/// The exponent must be between -28 and 28.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Parser};
+	///
+	/// const PARSER: Parser = Parser::new().with_exponents(true);
+	/// assert_eq!(PARSER.parse("1e3ms"), Ok(Duration::from_secs(1)));
+	/// assert_eq!(PARSER.parse("2.5E-3s"), Ok(Duration::from_micros(2500)));
+	/// ```
+	pub const fn with_exponents(mut self, enabled: bool) -> Self {
+		self.numbers.exponents = enabled;
+		self
+	}
+
+	/// Enables or disables digit separators in numbers: `_` anywhere after
+	/// the first digit, as in `1_000ms`, and `,` between groups of three digits
+	/// of the integer part, as in `1,000,000ns`.
+	///
+	/// A comma that isn't followed by exactly three digits still separates
+	/// two parts of the duration, as in `1h,30m`.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Parser};
+	///
+	/// const PARSER: Parser = Parser::new().with_digit_separators(true);
+	/// assert_eq!(PARSER.parse("1_000ms"), Ok(Duration::from_secs(1)));
+	/// assert_eq!(PARSER.parse("1,000,000ns"), Ok(Duration::from_millis(1)));
+	/// assert_eq!(PARSER.parse("1h,30m"), Ok(Duration::from_mins(90)));
+	/// ```
+	pub const fn with_digit_separators(mut self, enabled: bool) -> Self {
+		self.numbers.separators = enabled;
+		self
+	}
+
+	/// Enables or disables rational numbers, such as `1/2h` or `2.5/3m`.
+	///
+	/// The denominator must be a positive integer; the value is computed
+	/// exactly, then truncated to whole nanoseconds.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, ErrorKind, Parser};
+	///
+	/// const PARSER: Parser = Parser::new().with_rationals(true);
+	/// assert_eq!(PARSER.parse("1/2h"), Ok(Duration::from_mins(30)));
+	/// assert_eq!(PARSER.parse("1/3s"), Ok(Duration::from_nanos(333_333_333)));
+	/// assert_eq!(
+	///     PARSER.parse("1/0h").map_err(|e| e.kind()),
+	///     Err(ErrorKind::DivisionByZero)
+	/// );
+	/// ```
+	pub const fn with_rationals(mut self, enabled: bool) -> Self {
+		self.numbers.rationals = enabled;
+		self
+	}
+
+	/// Enables or disables Unicode vulgar fractions, on their own or after an
+	/// integer, such as `½h` or `1¾h`.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Parser};
+	///
+	/// const PARSER: Parser = Parser::new().with_vulgar_fractions(true);
+	/// assert_eq!(PARSER.parse("1½h"), Ok(Duration::from_mins(90)));
+	/// assert_eq!(PARSER.parse("⅓h"), Ok(Duration::from_mins(20)));
+	/// ```
+	pub const fn with_vulgar_fractions(mut self, enabled: bool) -> Self {
+		self.numbers.vulgar_fractions = enabled;
+		self
+	}
+
+	/// Sets how fractions of a nanosecond are handled.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, ErrorKind, Parser, Precision};
+	///
+	/// assert_eq!(Parser::new().parse("2.5ns"), Ok(Duration::from_nanos(2)));
+	///
+	/// let parser = Parser::new().with_precision(Precision::RoundHalfEven);
+	/// assert_eq!(parser.parse("2.5ns"), Ok(Duration::from_nanos(2)));
+	/// assert_eq!(parser.parse("2.51ns"), Ok(Duration::from_nanos(3)));
+	///
+	/// let parser = Parser::new().with_precision(Precision::Exact);
+	/// let e = parser.parse("1h 1.0000000001s").unwrap_err();
+	/// assert_eq!(e.kind(), ErrorKind::PrecisionLoss);
+	/// assert_eq!(e.span(), 3..16);
+	/// ```
+	pub const fn with_precision(mut self, precision: Precision) -> Self {
+		self.precision = precision;
+		self
+	}
+
+	/// Returns the custom units of this parser.
+	pub const fn units(&self) -> &'static [CustomUnit] {
+		self.units
+	}
+
+	/// Returns true if the [natural-language mode](Self::with_natural_language)
+	/// is enabled.
+	pub const fn is_natural_language(&self) -> bool {
+		self.natural
+	}
+
+	/// Parses a unit at the start of `input`.
+	///
+	/// Returns the rest of the input and the length of the unit in nanoseconds
+	/// as a `(numerator, denominator)` pair.
+	/// The span of the error is relative to `input`.
+	pub(crate) fn parse_unit<'a>(&self, input: &'a str) -> Result<(&'a str, (u128, u128)), Error> {
+		if input.trim().is_empty() {
+			return Err(Error::new(ErrorKind::MissingUnit, 0..input.len()));
+		}
+
+		let s = input.as_bytes();
+		let mut best = match_unit(s).map(|(unit, len)| (len, (unit.nanos(), 1)));
+		for unit in self.units {
+			if let Some(len) = unit.match_len(s) {
+				if best.is_none_or(|(n, _)| len > n) {
+					best = Some((len, (unit.numerator, unit.denominator)));
+				}
+			}
+		}
+
+		match best {
+			Some((len, unit)) if !input[len..].starts_with(|c: char| c.is_alphabetic()) => {
+				Ok((&input[len..], unit))
+			}
+			_ => {
+				// The unit is the word at the start of the input, or the first
+				// character if it doesn't start with a letter.
+				let len = match input.find(|c: char| !c.is_alphabetic()) {
+					Some(0) => input.chars().next().map_or(0, char::len_utf8),
+					Some(n) => n,
+					None => input.len(),
+				};
+				Err(Error::new(ErrorKind::InvalidUnit, 0..len)
+					.with_suggestion(unit::suggest(&input[..len], self.units)))
+			}
+		}
+	}
+
+	/// Parses a number at the start of `input` with the enabled extensions.
+	///
+	/// The span of the error is relative to `input`; a missing number is an
+	/// [ErrorKind::InvalidDuration] spanning the next word.
+	pub(crate) fn parse_number<'a>(&self, input: &'a str) -> Result<(&'a str, Number), Error> {
+		number::number(input, self.numbers)?.ok_or_else(|| {
+			let len = input.find(char::is_whitespace).unwrap_or(input.len());
+			Error::new(ErrorKind::InvalidDuration, 0..len)
+		})
+	}
+
+	/// Parses a duration with the built-in and the custom units.
+	///
+	/// See [parse] for the syntax.
+	pub fn parse(&self, input: &str) -> Result<Duration, Error> {
+		self.parse_with_report(input).map(|(d, _)| d)
+	}
+
+	/// Like [Parser::parse], but also returns the fraction of a nanosecond
+	/// discarded by the [Precision] policy: the exact value minus the
+	/// returned duration, in nanoseconds.
+	///
+	/// The remainder is negative if the value was rounded up.
+	/// It's the sum of the remainders of every part of the input, so it can be
+	/// more than a nanosecond, as in `0.5ns 0.5ns`.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Decimal, Duration, Parser, Precision};
+	///
+	/// assert_eq!(
+	///     Parser::new().parse_with_report("1.25ns"),
+	///     Ok((Duration::from_nanos(1), Decimal::new(25, 2)))
+	/// );
+	///
+	/// let parser = Parser::new().with_precision(Precision::RoundHalfEven);
+	/// assert_eq!(
+	///     parser.parse_with_report("1.75ns"),
+	///     Ok((Duration::from_nanos(2), Decimal::new(-25, 2)))
+	/// );
+	/// ```
+	pub fn parse_with_report(&self, input: &str) -> Result<(Duration, Decimal), Error> {
+		self.parse_spanned(input).map_err(|e| e.with_input(input))
+	}
+
+	/// Parses a duration at the start of `input`, returning it and the rest of
+	/// the input.
+	///
+	/// Unlike [Parser::parse], the input doesn't have to be a duration as a
+	/// whole: parsing stops before the first part that isn't a number
+	/// followed by a unit, along with the separator before it.
+	/// A number without a unit isn't a duration here.
+	///
+	/// #### Errors
+	/// Returns an error if the input doesn't start with a duration or a part
+	/// of it is invalid, such as a value that's too big.
+	///
+	/// #### Examples
+	/// ```rust
+	/// use dur::{Duration, Parser};
+	///
+	/// let parser = Parser::new();
+	/// assert_eq!(
+	///     parser.parse_prefix("1h 30m) then wait"),
+	///     Ok((Duration::from_mins(90), ") then wait"))
+	/// );
+	/// assert_eq!(
+	///     parser.parse_prefix("5s, 3 retries"),
+	///     Ok((Duration::from_secs(5), ", 3 retries"))
+	/// );
+	/// assert!(parser.parse_prefix("3 retries").is_err());
+	/// ```
+	pub fn parse_prefix<'a>(&self, input: &'a str) -> Result<(Duration, &'a str), Error> {
+		self.components(input, true)
+			.map(|(d, _, rem)| (d, rem))
+			.map_err(|e| e.with_input(input))
+	}
+
+	/// Converts `n` units to nanoseconds with the [Precision] policy of this
+	/// parser, returning the discarded remainder too.
+	pub(crate) fn nanos(
+		&self,
+		n: Number,
+		unit: (u128, u128),
+	) -> Result<(u128, Decimal), ErrorKind> {
+		nanos(n, unit, self.precision)
+	}
+
+	fn parse_spanned(&self, input: &str) -> Result<(Duration, Decimal), Error> {
+		let everything = 0..input.len();
+		if input.trim().is_empty() {
+			return Err(Error::new(ErrorKind::InvalidDuration, everything));
+		}
+		// A lone number is in milliseconds; the extended syntax allows lone
+		// numbers such as `1/2`.
+		let lone = match input.parse::<Decimal>() {
+			Ok(value) => Some(Number {
+				value,
+				denominator: 1,
+			}),
+			Err(_) if self.numbers.any() => match self.parse_number(input) {
+				Ok(("", n)) => Some(n),
+				_ => None,
+			},
+			Err(_) => None,
+		};
+		if let Some(n) = lone {
+			if n.value.is_sign_negative() {
+				return Err(Error::new(
+					ErrorKind::IsNegative(n.to_decimal()),
+					everything,
+				));
+			}
+			return self
+				.nanos(n, (MILLISECOND, 1))
+				.map(|(n, rem)| (Duration(n), rem))
+				.map_err(|kind| Error::new(kind, everything));
+		}
+		self.components(input, false)
+			.map(|(d, remainder, _)| (d, remainder))
+	}
+
+	/// Parses the parts of a duration at the start of `input`, returning the
+	/// duration, the remainder and the rest of the input.
+	///
+	/// If `prefix` is false, the whole input must be a duration.
+	/// Otherwise parsing stops before the first part after the first that
+	/// isn't a number followed by a unit.
+	fn components<'a>(
+		&self,
+		input: &'a str,
+		prefix: bool,
+	) -> Result<(Duration, Decimal, &'a str), Error> {
+		if self.natural {
+			return natural::parse(self, input, prefix);
+		}
+
+		let mut s = input;
+		let mut n = 0_u128;
+		let mut remainder = Decimal::ZERO;
+
+		for i in 0.. {
+			let before = s;
+			if i != 0 {
+				// Parts are separated by a comma and/or spaces.
+				s = s.strip_prefix(',').unwrap_or(s);
+				s = s.trim_start_matches([' ', '\t']);
+				if s.is_empty() {
+					break;
+				}
+			}
+
+			match self.component(input, s, n) {
+				Ok((ns, rem_ns, rem)) => {
+					n = ns;
+					remainder += rem_ns;
+					s = rem;
+				}
+				Err(e) if prefix && i != 0 && is_not_a_part(e.kind()) => {
+					return Ok((Duration(n), remainder, before))
+				}
+				Err(e) => return Err(e),
+			}
+		}
+
+		Ok((Duration(n), remainder, s))
+	}
+
+	/// Parses a number and a unit at the start of `s` and adds them to `n`
+	/// nanoseconds, returning the sum, the remainder and the rest of the input.
+	///
+	/// `s` must be a suffix of `input`, which the error spans are relative to.
+	fn component<'a>(
+		&self,
+		input: &str,
+		s: &'a str,
+		n: u128,
+	) -> Result<(u128, Decimal, &'a str), Error> {
+		let pos = |s: &str| input.len() - s.len();
+		let start = pos(s);
+		let (rem, d) = self.parse_number(s).map_err(|e| e.offset(start))?;
+		let number = start..pos(rem);
+
+		if d.value.is_sign_negative() {
+			return Err(Error::new(ErrorKind::IsNegative(d.to_decimal()), number));
+		}
+
+		let rem = rem.trim_start_matches([' ', '\t']);
+		let (rem, unit) = self.parse_unit(rem).map_err(|e| match e.kind() {
+			// Point at the number without a unit.
+			ErrorKind::MissingUnit => Error::new(ErrorKind::MissingUnit, number.clone()),
+			_ => e.offset(pos(rem)),
+		})?;
+		self.nanos(d, unit)
+			.and_then(|(ns, rem)| Ok((n.checked_add(ns).ok_or(ErrorKind::ValueTooBig)?, rem)))
+			.map(|(ns, rem_ns)| (ns, rem_ns, rem))
+			.map_err(|kind| Error::new(kind, start..pos(rem)))
+	}
+
+	/// Returns a struct that displays `d` using the custom units of this
+	/// parser where they fit.
+	///
+	/// See [CustomDisplay] for details.
+	pub const fn display(&self, d: Duration) -> CustomDisplay {
+		CustomDisplay {
+			d,
+			units: self.units,
+		}
+	}
+}
+
+/// Returns true if an error of kind `kind` means that the input doesn't start
+/// with a number followed by a unit, as opposed to an invalid one.
+pub(crate) const fn is_not_a_part(kind: ErrorKind) -> bool {
+	matches!(
+		kind,
+		ErrorKind::InvalidDuration | ErrorKind::MissingUnit | ErrorKind::InvalidUnit
+	)
+}
+
+/// Returns `n` units of `numerator / denominator` nanoseconds each in whole
+/// nanoseconds according to `precision`, and the discarded remainder.
+fn nanos(
+	n: Number,
+	(numerator, denominator): (u128, u128),
+	precision: Precision,
+) -> Result<(u128, Decimal), ErrorKind> {
+	let d = Decimal::from(numerator)
+		.checked_mul(n.value)
+		.ok_or(ErrorKind::ValueTooBig)?;
+	let m = d.mantissa().unsigned_abs();
+	// `d` is `m / 10^scale` with `m` below 2^96, so the quotient is zero if
+	// the divisor doesn't fit.
+	let divisor = 10_u128
+		.checked_pow(d.scale())
+		.and_then(|p| p.checked_mul(denominator))
+		.and_then(|p| p.checked_mul(n.denominator));
+	let (q, r, remainder) = match divisor {
+		Some(divisor) => (m / divisor, m % divisor, ratio(m % divisor, divisor)),
+		None => (
+			0,
+			m,
+			d.checked_div(Decimal::from(denominator))
+				.zip(to_dec(n.denominator))
+				.and_then(|(d, den)| d.checked_div(den))
+				.unwrap_or(Decimal::ZERO),
+		),
+	};
+
+	match precision {
+		_ if r == 0 => Ok((q, Decimal::ZERO)),
+		Precision::Truncate => Ok((q, remainder)),
+		Precision::Exact => Err(ErrorKind::PrecisionLoss),
+		Precision::RoundHalfEven => {
+			// Compare `r / divisor` to a half; ties go to the even neighbor.
+			let up = match divisor.map(|divisor| r.cmp(&(divisor - r))) {
+				Some(Ordering::Greater) => true,
+				Some(Ordering::Equal) => q % 2 == 1,
+				Some(Ordering::Less) | None => false,
+			};
+			if up {
+				let q = q.checked_add(1).ok_or(ErrorKind::ValueTooBig)?;
+				Ok((q, remainder - Decimal::ONE))
+			} else {
+				Ok((q, remainder))
+			}
+		}
+	}
+}
+
+/// Returns `r / divisor` as a [Decimal], approximately if they're too big.
+fn ratio(mut r: u128, mut divisor: u128) -> Decimal {
+	while divisor >> 96 != 0 {
+		r >>= 1;
+		divisor >>= 1;
+	}
+	Decimal::from(r) / Decimal::from(divisor)
+}
+
+/// How a [Parser] handles values that aren't whole nanoseconds, such as
+/// `0.5ns` or `1.0000000001s`.
+///
+/// Every part of the input is converted separately, so `0.5ns 0.5ns` is
+/// zero when truncated.
+/// [Parser::parse_with_report] returns the discarded remainder.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Precision {
+	/// Discard the fraction of a nanosecond. This is what [parse] does.
+	#[default]
+	Truncate,
+	/// Round to the nearest nanosecond, and to the even one on ties.
+	RoundHalfEven,
+	/// Fail with [ErrorKind::PrecisionLoss].
+	Exact,
+}
+
+/// Displays a [Duration] with custom units.
+///
+/// The duration is broken down into the largest custom unit that fits and is
+/// longer than the largest built-in unit that fits, repeatedly; the remainder
+/// is printed like a [Duration].
+/// Custom units are always printed with their full name, e.g. `2 sprints 3d`,
+/// while the formatting flags are forwarded to the remainder.
+///
+/// Obtained with [Parser::display].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CustomDisplay {
+	d: Duration,
+	units: &'static [CustomUnit],
+}
+
+impl Display for CustomDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// The units used by the `Display` implementation of `Duration`.
+		const BUILT_IN: [u128; 8] = [YEAR, DAY, HOUR, MINUTE, SECOND, MILLISECOND, MICROSECOND, 1];
+
+		let mut ns = self.d.0;
+		let mut first = true;
+
+		loop {
+			let built_in = BUILT_IN.into_iter().find(|&u| u <= ns).unwrap_or(1);
+			let custom = self
+				.units
+				.iter()
+				.filter(|u| {
+					u.numerator > built_in.saturating_mul(u.denominator) && u.count(ns) != 0
+				})
+				// Compare `a.numerator / a.denominator` to `b.numerator / b.denominator`.
+				.max_by(|a, b| {
+					Decimal::from(a.numerator)
+						.checked_div(Decimal::from(a.denominator))
+						.cmp(&Decimal::from(b.numerator).checked_div(Decimal::from(b.denominator)))
+				});
+
+			let Some(unit) = custom else {
+				if ns != 0 || first {
+					if !first {
+						f.write_str(" ")?;
+					}
+					Display::fmt(&Duration(ns), f)?;
+				}
+				return Ok(());
+			};
+
+			if !first {
+				f.write_str(" ")?;
+			}
+			first = false;
+
+			let n = unit.count(ns);
+			if n == 1 {
+				write!(f, "1 {}", unit.name)?;
+			} else {
+				write!(f, "{n} {}s", unit.name)?;
+			}
+			ns = ns.saturating_sub(unit.length(n).unwrap_or(u128::MAX));
+		}
+	}
+}
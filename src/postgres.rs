@@ -0,0 +1,542 @@
+use core::ops::Range;
+
+use crate::*;
+
+const USECS_PER_SEC: i128 = 1_000_000;
+const USECS_PER_MINUTE: i128 = 60 * USECS_PER_SEC;
+const USECS_PER_HOUR: i128 = 60 * USECS_PER_MINUTE;
+const USECS_PER_DAY: i128 = 24 * USECS_PER_HOUR;
+
+/// A PostgreSQL `interval`, stored the way Postgres stores it: as months, days
+/// and microseconds, each with its own sign.
+///
+/// Months and days don't have a fixed length in Postgres, so they're kept
+/// apart; use [PgInterval::to_duration] to get a [Duration].
+///
+/// It implements [FromStr] for all four `IntervalStyle` outputs, see
+/// [parse_postgres], and [Display] in the default `postgres` style.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, PgInterval};
+///
+/// let i = "1 year 2 mons 3 days 04:05:06".parse::<PgInterval>().unwrap();
+/// assert_eq!(
+///     i,
+///     PgInterval {
+///         months: 14,
+///         days: 3,
+///         microseconds: 14_706_000_000,
+///     }
+/// );
+/// assert_eq!(i.to_string(), "1 year 2 mons 3 days 04:05:06");
+/// assert_eq!(i.to_duration(), None);
+///
+/// let i = "-1 -2:00:00".parse::<PgInterval>().unwrap();
+/// assert_eq!(i.to_string(), "-1 days -02:00:00");
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct PgInterval {
+	pub months: i32,
+	pub days: i32,
+	pub microseconds: i64,
+}
+
+impl PgInterval {
+	/// Converts the interval to a [Duration], counting a day as 24 hours.
+	///
+	/// Returns [None] if the interval has months, which don't have a fixed
+	/// length, or if it's negative.
+	pub fn to_duration(self) -> Option<Duration> {
+		if self.months != 0 {
+			return None;
+		}
+		let us = i128::from(self.days) * USECS_PER_DAY + i128::from(self.microseconds);
+		u128::try_from(us).ok().map(|us| Duration(us * MICROSECOND))
+	}
+}
+
+impl FromStr for PgInterval {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_interval(s)
+			.map(|(i, _)| i)
+			.map_err(|e| e.with_input(s))
+	}
+}
+
+impl Display for PgInterval {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write_postgres(
+			f,
+			self.months.into(),
+			self.days.into(),
+			self.microseconds.into(),
+		)
+	}
+}
+
+/// Implements [Display] in the `postgres` `IntervalStyle` of PostgreSQL, its
+/// default, such as `1 day 02:03:04.5` or `00:00:00`.
+///
+/// Whole days are written as days, the rest as `hh:mm:ss`.
+/// Postgres counts microseconds, so shorter durations are truncated.
+///
+/// The only way to obtain a value of this struct is via the [format_postgres](Duration::format_postgres) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// let d = Duration::from_millis(93_784_500);
+/// assert_eq!(d.format_postgres().to_string(), "1 day 02:03:04.5");
+/// assert_eq!(Duration::ZERO.format_postgres().to_string(), "00:00:00");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PostgresDisplay(pub(crate) u128);
+
+impl Display for PostgresDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// Days fit easily, since a u128 of nanoseconds is about 4e24 days.
+		let days = (self.0 / DAY) as i128;
+		let us = (self.0 % DAY / MICROSECOND) as i128;
+		write_postgres(f, 0, days, us)
+	}
+}
+
+/// Writes a field of the `postgres` style, such as ` +3 days`, the way
+/// Postgres' `AddPostgresIntPart` does.
+fn write_part(
+	f: &mut Formatter,
+	value: i128,
+	unit: &str,
+	is_zero: &mut bool,
+	is_before: &mut bool,
+) -> fmt::Result {
+	if value == 0 {
+		return Ok(());
+	}
+	let sep = if *is_zero { "" } else { " " };
+	let sign = if *is_before && value > 0 { "+" } else { "" };
+	let plural = if value != 1 { "s" } else { "" };
+	write!(f, "{sep}{sign}{value} {unit}{plural}")?;
+	*is_before = value < 0;
+	*is_zero = false;
+	Ok(())
+}
+
+/// Writes an interval in the `postgres` style, the way Postgres'
+/// `EncodeInterval` does.
+fn write_postgres(f: &mut Formatter, months: i128, days: i128, us: i128) -> fmt::Result {
+	let (mut is_zero, mut is_before) = (true, false);
+	// The year and the month have the same sign.
+	write_part(f, months / 12, "year", &mut is_zero, &mut is_before)?;
+	write_part(f, months % 12, "mon", &mut is_zero, &mut is_before)?;
+	write_part(f, days, "day", &mut is_zero, &mut is_before)?;
+
+	if is_zero || us != 0 {
+		let sep = if is_zero { "" } else { " " };
+		let sign = match () {
+			_ if us < 0 => "-",
+			_ if is_before => "+",
+			_ => "",
+		};
+		let us = us.unsigned_abs() as i128;
+		let (hours, mins, secs) = (
+			us / USECS_PER_HOUR,
+			us / USECS_PER_MINUTE % 60,
+			us / USECS_PER_SEC % 60,
+		);
+		write!(f, "{sep}{sign}{hours:02}:{mins:02}:{secs:02}")?;
+
+		let (mut frac, mut digits) = (us % USECS_PER_SEC, 6);
+		if frac != 0 {
+			while frac % 10 == 0 {
+				frac /= 10;
+				digits -= 1;
+			}
+			write!(f, ".{frac:0digits$}")?;
+		}
+	}
+	Ok(())
+}
+
+/// Parses a PostgreSQL `interval` in any of the `IntervalStyle` output
+/// formats, as a [Duration].
+///
+/// All four styles are recognized:
+///
+/// | Style              | Example                                        |
+/// |--------------------|------------------------------------------------|
+/// | `postgres`         | `1 year 2 mons 3 days 04:05:06.5`              |
+/// | `postgres_verbose` | `@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs` |
+/// | `sql_standard`     | `1-2 3 4:05:06.5`                              |
+/// | `iso_8601`         | `P1Y2M3DT4H5M6.5S`                             |
+///
+/// Each field can have its own sign, as in `-1 days +02:00:00`, and a day is
+/// counted as 24 hours.
+/// Use [PgInterval] for intervals with months or years.
+///
+/// #### Errors
+/// Returns an error if the input isn't an interval in one of the styles, if
+/// it has months or years, which is an [ErrorKind::CalendarUnit], or if it's
+/// negative, which is an [ErrorKind::IsNegative] holding the value in
+/// nanoseconds.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ErrorKind};
+///
+/// let d = Duration::from_millis(93_784_500);
+/// assert_eq!(dur::parse_postgres("1 day 02:03:04.5"), Ok(d));
+/// assert_eq!(dur::parse_postgres("@ 1 day 2 hours 3 mins 4.5 secs"), Ok(d));
+/// assert_eq!(dur::parse_postgres("1 2:03:04.5"), Ok(d));
+/// assert_eq!(dur::parse_postgres("P1DT2H3M4.5S"), Ok(d));
+///
+/// assert_eq!(
+///     dur::parse_postgres("3 mons 2 days").map_err(|e| e.kind()),
+///     Err(ErrorKind::CalendarUnit)
+/// );
+/// ```
+pub fn parse_postgres(input: &str) -> Result<Duration, Error> {
+	parse_postgres_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn parse_postgres_spanned(input: &str) -> Result<Duration, Error> {
+	let (i, months) = parse_interval(input)?;
+	if let Some(span) = months.filter(|_| i.months != 0) {
+		return Err(Error::new(ErrorKind::CalendarUnit, span));
+	}
+	let ns = (i128::from(i.days) * USECS_PER_DAY + i128::from(i.microseconds)) * 1000;
+	match u128::try_from(ns) {
+		Ok(ns) => Ok(Duration(ns)),
+		// At most about 2e23, which a Decimal can hold.
+		Err(_) => Err(Error::new(
+			ErrorKind::IsNegative(Decimal::from_i128_with_scale(ns, 0)),
+			0..input.len(),
+		)),
+	}
+}
+
+/// The fields of an interval being parsed.
+#[derive(Default)]
+struct Fields {
+	months: i128,
+	days: i128,
+	us: i128,
+	/// The span of the first field with months.
+	months_span: Option<Range<usize>>,
+}
+
+/// The fields of an interval.
+#[derive(Copy, Clone)]
+enum Kind {
+	Months,
+	Days,
+	Micros,
+}
+
+impl Fields {
+	fn add(&mut self, kind: Kind, n: i128, span: Range<usize>) -> Result<(), Error> {
+		let too_big = Error::new(ErrorKind::ValueTooBig, span.clone());
+		let sum = match kind {
+			Kind::Months => {
+				if n != 0 && self.months_span.is_none() {
+					self.months_span = Some(span);
+				}
+				&mut self.months
+			}
+			Kind::Days => &mut self.days,
+			Kind::Micros => &mut self.us,
+		};
+		*sum = sum.checked_add(n).ok_or(too_big)?;
+		Ok(())
+	}
+
+	fn negate(&mut self) {
+		self.months = -self.months;
+		self.days = -self.days;
+		self.us = -self.us;
+	}
+
+	fn finish(self, input: &str) -> Result<(PgInterval, Option<Range<usize>>), Error> {
+		let too_big = || Error::new(ErrorKind::ValueTooBig, 0..input.len());
+		let i = PgInterval {
+			months: self.months.try_into().map_err(|_| too_big())?,
+			days: self.days.try_into().map_err(|_| too_big())?,
+			microseconds: self.us.try_into().map_err(|_| too_big())?,
+		};
+		Ok((i, self.months_span))
+	}
+}
+
+/// Splits `input` at whitespace, returning the offset and the text of each
+/// token.
+fn tokens(input: &str) -> impl Iterator<Item = (usize, &str)> {
+	let mut pos = 0;
+	core::iter::from_fn(move || {
+		let rest = &input[pos..];
+		let start = pos + rest.len() - rest.trim_start().len();
+		let len = input[start..]
+			.find(char::is_whitespace)
+			.unwrap_or(input.len() - start);
+		pos = start + len;
+		(len != 0).then(|| (start, &input[start..pos]))
+	})
+}
+
+/// Returns true if `s` is `[0-9]+`.
+fn is_digits(s: &str) -> bool {
+	!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Returns true if `s` is `[+-]?[0-9]+`, or `[+-]?[0-9]+(\.[0-9]+)?` if
+/// `fraction` is true.
+fn is_number(s: &str, fraction: bool) -> bool {
+	let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+	match s.split_once('.') {
+		Some((int, frac)) => fraction && is_digits(int) && is_digits(frac),
+		None => is_digits(s),
+	}
+}
+
+/// Parses an integer checked with [is_number].
+fn int(s: &str, span: Range<usize>) -> Result<i128, Error> {
+	s.parse()
+		.map_err(|_| Error::new(ErrorKind::ValueTooBig, span))
+}
+
+/// Parses a number checked with [is_number] of `unit` microseconds each,
+/// rounding to whole microseconds like Postgres does.
+fn micros(s: &str, unit: i128, span: Range<usize>) -> Result<i128, Error> {
+	let too_big = || Error::new(ErrorKind::ValueTooBig, span.clone());
+	let n = parse_dec(s).map_err(|_| too_big())?;
+	let unit = Decimal::try_from_i128_with_scale(unit, 0).map_err(|_| too_big())?;
+	n.checked_mul(unit)
+		.map(|n| n.round_dp_with_strategy(0, rust_decimal::RoundingStrategy::MidpointNearestEven))
+		.and_then(|n| i128::try_from(n).ok())
+		.ok_or_else(too_big)
+}
+
+/// Parses a clock time such as `-04:05:06.5`, in microseconds.
+fn clock(s: &str, span: Range<usize>) -> Result<i128, Error> {
+	let invalid = || Error::new(ErrorKind::InvalidDuration, span.clone());
+	let (neg, rest) = match s.strip_prefix('-') {
+		Some(rest) => (true, rest),
+		None => (false, s.strip_prefix('+').unwrap_or(s)),
+	};
+	let mut parts = rest.splitn(3, ':');
+	let (hours, mins) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+	let secs = parts.next().unwrap_or("0");
+	let secs_ok = match secs.split_once('.') {
+		Some((int, frac)) => is_digits(int) && is_digits(frac),
+		None => is_digits(secs),
+	};
+	if !is_digits(hours) || !is_digits(mins) || !secs_ok {
+		return Err(invalid());
+	}
+
+	let mins = int(mins, span.clone())?;
+	let secs = micros(secs, USECS_PER_SEC, span.clone())?;
+	if mins >= 60 || secs >= USECS_PER_MINUTE {
+		return Err(invalid());
+	}
+	let us = int(hours, span.clone())?
+		.checked_mul(USECS_PER_HOUR)
+		.and_then(|us| us.checked_add(mins * USECS_PER_MINUTE + secs))
+		.ok_or(Error::new(ErrorKind::ValueTooBig, span))?;
+	Ok(if neg { -us } else { us })
+}
+
+/// Parses the value of a field of `unit` months, days or microseconds each.
+///
+/// Only microseconds can have a fraction, which is rounded.
+fn value(s: &str, kind: Kind, unit: i128, span: Range<usize>) -> Result<i128, Error> {
+	match kind {
+		Kind::Micros => micros(s, unit, span),
+		_ if is_number(s, false) => int(s, span.clone())?
+			.checked_mul(unit)
+			.ok_or(Error::new(ErrorKind::ValueTooBig, span)),
+		_ => Err(Error::new(ErrorKind::InvalidDuration, span)),
+	}
+}
+
+/// Parses the unit word of the `postgres` and `postgres_verbose` styles,
+/// returning what it counts and how many of it.
+fn unit_word(w: &str) -> Option<(Kind, i128)> {
+	let is = |words: &[&str]| words.iter().any(|x| x.eq_ignore_ascii_case(w));
+	if is(&["year", "years"]) {
+		Some((Kind::Months, 12))
+	} else if is(&["mon", "mons", "month", "months"]) {
+		Some((Kind::Months, 1))
+	} else if is(&["day", "days"]) {
+		Some((Kind::Days, 1))
+	} else if is(&["hour", "hours"]) {
+		Some((Kind::Micros, USECS_PER_HOUR))
+	} else if is(&["min", "mins", "minute", "minutes"]) {
+		Some((Kind::Micros, USECS_PER_MINUTE))
+	} else if is(&["sec", "secs", "second", "seconds"]) {
+		Some((Kind::Micros, USECS_PER_SEC))
+	} else {
+		None
+	}
+}
+
+/// What a pass of [words] found out about the input.
+struct Words {
+	fields: Fields,
+	/// Whether the input is in the `sql_standard` style.
+	sql: bool,
+	/// Whether only the first field has a sign, which is a minus.
+	negative_first: bool,
+}
+
+/// Parses the `postgres`, `postgres_verbose` and `sql_standard` styles.
+///
+/// If `negate_rest` is true, the fields after the first are negated.
+fn words(input: &str, negate_rest: bool) -> Result<Words, Error> {
+	let mut tokens = tokens(input).peekable();
+	let verbose = tokens.next_if(|&(_, t)| t == "@").is_some();
+	let mut fields = Fields::default();
+	let (mut sql, mut ago) = (false, false);
+	let (mut count, mut signs, mut negative_first) = (0, 0, false);
+
+	while let Some((pos, t)) = tokens.next() {
+		let mut span = pos..pos + t.len();
+		if verbose && count != 0 && t.eq_ignore_ascii_case("ago") && tokens.peek().is_none() {
+			ago = true;
+			break;
+		}
+
+		let (kind, n) = if t.contains(':') {
+			(Kind::Micros, clock(t, span.clone())?)
+		} else if is_number(t, true) {
+			match tokens.next_if(|(_, w)| w.starts_with(char::is_alphabetic)) {
+				Some((upos, w)) => {
+					let (kind, unit) = unit_word(w)
+						.ok_or(Error::new(ErrorKind::InvalidUnit, upos..upos + w.len()))?;
+					span.end = upos + w.len();
+					(kind, value(t, kind, unit, span.clone())?)
+				}
+				// `3 4:05:06`, a number of days.
+				None if is_number(t, false) => {
+					sql = true;
+					(Kind::Days, int(t, span.clone())?)
+				}
+				None => return Err(Error::new(ErrorKind::MissingUnit, span)),
+			}
+		} else if let Some((years, months)) = t
+			.strip_prefix(['+', '-'])
+			.unwrap_or(t)
+			.split_once('-')
+			.filter(|(y, m)| is_digits(y) && is_digits(m))
+		{
+			// `1-2`, years and months.
+			sql = true;
+			let months = int(months, span.clone())?;
+			if months >= 12 {
+				return Err(Error::new(ErrorKind::InvalidDuration, span));
+			}
+			let n = value(years, Kind::Months, 12, span.clone())?
+				.checked_add(months)
+				.ok_or(Error::new(ErrorKind::ValueTooBig, span.clone()))?;
+			(Kind::Months, if t.starts_with('-') { -n } else { n })
+		} else {
+			return Err(Error::new(ErrorKind::InvalidDuration, span));
+		};
+
+		if t.starts_with(['+', '-']) {
+			signs += 1;
+			negative_first |= count == 0 && t.starts_with('-');
+		}
+		let n = if negate_rest && count != 0 { -n } else { n };
+		fields.add(kind, n, span)?;
+		count += 1;
+	}
+
+	if count == 0 {
+		return Err(Error::new(ErrorKind::InvalidDuration, 0..input.len()));
+	}
+	if ago {
+		fields.negate();
+	}
+	Ok(Words {
+		fields,
+		sql,
+		negative_first: negative_first && signs == 1,
+	})
+}
+
+/// Parses the `iso_8601` style, such as `P1Y2M3DT4H5M6.5S`.
+fn iso(input: &str, start: usize) -> Result<Fields, Error> {
+	let s = input.trim_end();
+	let b = s.as_bytes();
+	let mut fields = Fields::default();
+	// Skip the `P`.
+	let mut i = start + 1;
+	let mut time = false;
+	if i == b.len() {
+		return Err(Error::new(ErrorKind::InvalidDuration, start..i));
+	}
+
+	while i < b.len() {
+		if !time && b[i] == b'T' && i + 1 < b.len() {
+			time = true;
+			i += 1;
+			continue;
+		}
+		let from = i;
+		i += usize::from(matches!(b[i], b'+' | b'-'));
+		i += b[i..]
+			.iter()
+			.take_while(|c| c.is_ascii_digit() || **c == b'.')
+			.count();
+		let number = &s[from..i];
+		if !is_number(number, true) {
+			let len = s[from..]
+				.chars()
+				.next()
+				.map_or(1, char::len_utf8)
+				.max(i - from);
+			return Err(Error::new(ErrorKind::InvalidDuration, from..from + len));
+		}
+		let Some(&designator) = b.get(i) else {
+			return Err(Error::new(ErrorKind::MissingUnit, from..i));
+		};
+		let unit_len = s[i..].chars().next().map_or(1, char::len_utf8);
+		i += unit_len;
+		let span = from..i;
+
+		let (kind, unit) = match (time, designator) {
+			(false, b'Y') => (Kind::Months, 12),
+			(false, b'M') => (Kind::Months, 1),
+			(false, b'W') => (Kind::Days, 7),
+			(false, b'D') => (Kind::Days, 1),
+			(true, b'H') => (Kind::Micros, USECS_PER_HOUR),
+			(true, b'M') => (Kind::Micros, USECS_PER_MINUTE),
+			(true, b'S') => (Kind::Micros, USECS_PER_SEC),
+			_ => return Err(Error::new(ErrorKind::InvalidUnit, i - unit_len..i)),
+		};
+		fields.add(kind, value(number, kind, unit, span.clone())?, span)?;
+	}
+	Ok(fields)
+}
+
+/// Parses an interval in any style, returning it and the span of the first
+/// field with months.
+fn parse_interval(input: &str) -> Result<(PgInterval, Option<Range<usize>>), Error> {
+	let start = input.len() - input.trim_start().len();
+	if input[start..].starts_with('P') {
+		return iso(input, start)?.finish(input);
+	}
+
+	let w = words(input, false)?;
+	if w.sql && w.negative_first {
+		// In the `sql_standard` style, a leading minus applies to every field,
+		// as in `-1 2:03:04`.
+		words(input, true)?.fields.finish(input)
+	} else {
+		w.fields.finish(input)
+	}
+}
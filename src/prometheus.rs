@@ -0,0 +1,158 @@
+use crate::*;
+
+/// The largest duration Prometheus accepts, in nanoseconds.
+const PROMETHEUS_MAX: u128 = i64::MAX as u128;
+
+/// A Prometheus year, 365 days.
+const PROMETHEUS_YEAR: u128 = 365 * DAY;
+
+/// The units of Prometheus from the largest, which is the order they must be
+/// written in.
+const UNITS: [(&str, u128); 7] = [
+	("y", PROMETHEUS_YEAR),
+	("w", WEEK),
+	("d", DAY),
+	("h", HOUR),
+	("m", MINUTE),
+	("s", SECOND),
+	("ms", MILLISECOND),
+];
+
+/// Implements [Display] in the duration format of Prometheus and Grafana,
+/// such as `1h30m`, `2w` or `90d`.
+///
+/// The output matches Prometheus' `model.Duration.String`: there are no
+/// spaces, years and weeks are only used if they divide the duration evenly,
+/// a year is 365 days and zero is `0s`.
+/// Prometheus counts milliseconds, so shorter durations are truncated.
+///
+/// The only way to obtain a value of this struct is via the [format_prometheus](Duration::format_prometheus) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(Duration::from_secs(5400).format_prometheus().to_string(), "1h30m");
+/// assert_eq!(Duration::from_days(14).format_prometheus().to_string(), "2w");
+/// assert_eq!(Duration::from_days(90).format_prometheus().to_string(), "90d");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PrometheusDisplay(pub(crate) u128);
+
+impl Display for PrometheusDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut n = self.0 - self.0 % MILLISECOND;
+		if n == 0 {
+			return f.write_str("0s");
+		}
+
+		for (i, (suffix, unit)) in UNITS.into_iter().enumerate() {
+			// Only years and weeks that divide the duration evenly, since
+			// `90d` reads better than `12w6d`.
+			if i < 2 && !n.is_multiple_of(unit) {
+				continue;
+			}
+			if n >= unit {
+				write!(f, "{}{suffix}", n / unit)?;
+				n %= unit;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Parses a duration in the format of Prometheus and Grafana, such as `5m`,
+/// `1h30m` or `2w`.
+///
+/// This follows Prometheus' `model.ParseDuration`: a duration is a sequence
+/// of whole numbers followed by a unit, with no spaces and at most one of
+/// each unit, from the largest to the smallest.
+/// The units are `y`, `w`, `d`, `h`, `m`, `s` and `ms`, where a year is 365
+/// days, and `0` is the only number allowed without a unit.
+///
+/// #### Errors
+/// Returns an error if the input isn't a valid duration for Prometheus, such
+/// as `1.5h`, `1h 30m` or `30m1h`, or if it's longer than Prometheus allows,
+/// about 292 years.
+/// An out of order or repeated unit is an [ErrorKind::InvalidDuration]
+/// spanning the part with it.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ErrorKind};
+///
+/// assert_eq!(dur::parse_prometheus("1h30m"), Ok(Duration::from_secs(5400)));
+/// assert_eq!(dur::parse_prometheus("1y"), Ok(Duration::from_days(365)));
+///
+/// let e = dur::parse_prometheus("30m1h").unwrap_err();
+/// assert_eq!((e.kind(), e.span()), (ErrorKind::InvalidDuration, 3..5));
+/// assert!(dur::parse_prometheus("1.5h").is_err());
+/// assert!(dur::parse_prometheus("1h 30m").is_err());
+/// ```
+pub fn parse_prometheus(input: &str) -> Result<Duration, Error> {
+	parse_prometheus_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn parse_prometheus_spanned(input: &str) -> Result<Duration, Error> {
+	if input == "0" {
+		return Ok(Duration(0));
+	}
+	if input.is_empty() {
+		return Err(Error::new(ErrorKind::InvalidDuration, 0..0));
+	}
+
+	let s = input.as_bytes();
+	let invalid_char = |i: usize| {
+		let len = input[i..].chars().next().map_or(0, char::len_utf8);
+		Error::new(ErrorKind::InvalidDuration, i..i + len)
+	};
+
+	let mut i = 0;
+	let mut total = 0_u128;
+	let mut next_unit = 0;
+	while i < s.len() {
+		let start = i;
+		let digits = s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+		if digits == 0 {
+			return Err(invalid_char(i));
+		}
+		i += digits;
+		let number = start..i;
+
+		let len = s[i..]
+			.iter()
+			.take_while(|c| c.is_ascii_alphabetic())
+			.count();
+		if len == 0 {
+			return Err(match s.get(i) {
+				Some(_) => invalid_char(i),
+				None => Error::new(ErrorKind::MissingUnit, number),
+			});
+		}
+		let unit = &input[i..i + len];
+		i += len;
+		let (pos, &(_, mult)) = UNITS
+			.iter()
+			.enumerate()
+			.find(|(_, (u, _))| *u == unit)
+			.ok_or(Error::new(ErrorKind::InvalidUnit, i - len..i))?;
+		if pos < next_unit {
+			// Units must go from the largest to the smallest.
+			return Err(Error::new(ErrorKind::InvalidDuration, start..i));
+		}
+		next_unit = pos + 1;
+
+		let too_big = Error::new(ErrorKind::ValueTooBig, start..i);
+		let n = input[number]
+			.parse::<u128>()
+			.ok()
+			.filter(|&n| n <= PROMETHEUS_MAX / mult)
+			.ok_or(too_big.clone())?;
+		total += n * mult;
+		if total > PROMETHEUS_MAX {
+			return Err(too_big);
+		}
+	}
+
+	Ok(Duration(total))
+}
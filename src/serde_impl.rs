@@ -1,19 +1,36 @@
-use alloc::string::ToString;
-use core::fmt;
+use alloc::{
+	string::ToString,
+	vec::Vec,
+};
+use core::{
+	fmt,
+	marker::PhantomData,
+};
 
 use serde::{
 	de::{
 		self,
 		Visitor,
 	},
+	ser::{
+		SerializeSeq,
+		SerializeStruct,
+	},
 	Deserialize,
 	Serialize,
 	Serializer,
 };
 
 use crate::{
+	dialect::Dialect,
+	granularity::Granularity,
+	histogram,
 	serde_impl::de::Deserializer,
+	BoundedDuration,
 	Duration,
+	DurationHistogram,
+	Timeout,
+	Whole,
 };
 
 impl Serialize for Duration {
@@ -86,3 +103,366 @@ impl<'de> Deserialize<'de> for Duration {
 		deserializer.deserialize_str(DurationVisitor)
 	}
 }
+
+// BoundedDuration
+
+impl<const MIN_NS: u128, const MAX_NS: u128> Serialize for BoundedDuration<MIN_NS, MAX_NS> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		self.get().serialize(serializer)
+	}
+}
+
+impl<'de, const MIN_NS: u128, const MAX_NS: u128> Deserialize<'de>
+	for BoundedDuration<MIN_NS, MAX_NS>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Self::try_from(Duration::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(e.to_string()))
+	}
+}
+
+// Whole
+
+impl<G: Granularity> Serialize for Whole<G> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de, G: Granularity> Deserialize<'de> for Whole<G> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Self::try_from(Duration::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(e.to_string()))
+	}
+}
+
+// Timeout
+
+impl Serialize for Timeout {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match self {
+			Self::Finite(d) => d.serialize(serializer),
+			Self::Infinite => serializer.serialize_str("infinity"),
+		}
+	}
+}
+
+struct TimeoutVisitor;
+
+impl<'de> Visitor<'de> for TimeoutVisitor {
+	type Value = Timeout;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a non-negative integer, a string describing a duration or \"infinity\"")
+	}
+
+	fn visit_u64<E>(self, n: u64) -> Result<Timeout, E>
+	where
+		E: de::Error,
+	{
+		DurationVisitor.visit_u64(n).map(Timeout::Finite)
+	}
+
+	fn visit_u128<E>(self, n: u128) -> Result<Timeout, E>
+	where
+		E: de::Error,
+	{
+		DurationVisitor.visit_u128(n).map(Timeout::Finite)
+	}
+
+	fn visit_str<E>(self, s: &str) -> Result<Timeout, E>
+	where
+		E: de::Error,
+	{
+		Timeout::parse(s).map_err(|e| E::custom(e.to_string()))
+	}
+}
+
+impl<'de> Deserialize<'de> for Timeout {
+	fn deserialize<D>(deserializer: D) -> Result<Timeout, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_str(TimeoutVisitor)
+	}
+}
+
+// Dialects
+
+/// De/serializes a [Duration] as a string in a [Dialect], for use with
+/// `#[serde(with = "dur::dialect::Serde::<D>")]`.
+///
+/// Unlike the [Deserialize] implementation of [Duration], numbers aren't
+/// accepted.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{
+///     dialect::{
+///         Go,
+///         Serde,
+///     },
+///     Duration,
+/// };
+///
+/// // What `#[serde(with = "Serde::<Go>")]` does.
+/// let mut json = Vec::new();
+/// Serde::<Go>::serialize(
+///     &Duration::from_secs(90),
+///     &mut serde_json::Serializer::new(&mut json),
+/// )
+/// .unwrap();
+/// assert_eq!(json, br#""1m30s""#);
+///
+/// let mut de = serde_json::Deserializer::from_str(r#""1h30m""#);
+/// assert_eq!(Serde::<Go>::deserialize(&mut de).unwrap(), Duration::from_mins(90));
+/// ```
+pub struct Serde<D>(PhantomData<D>);
+
+impl<D: Dialect> Serde<D> {
+	/// Serializes a duration as a string in the dialect.
+	pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(&d.display_as::<D>())
+	}
+
+	/// Deserializes a duration from a string in the dialect.
+	pub fn deserialize<'de, De>(deserializer: De) -> Result<Duration, De::Error>
+	where
+		De: Deserializer<'de>,
+	{
+		deserializer.deserialize_str(DialectVisitor::<D>(PhantomData))
+	}
+}
+
+struct DialectVisitor<D>(PhantomData<D>);
+
+impl<D: Dialect> Visitor<'_> for DialectVisitor<D> {
+	type Value = Duration;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a string describing a duration")
+	}
+
+	fn visit_str<E>(self, s: &str) -> Result<Duration, E>
+	where
+		E: de::Error,
+	{
+		D::parse(s).map_err(|e| E::custom(e.to_string()))
+	}
+}
+
+// DurationHistogram
+
+struct Buckets<'a, const N: usize>(&'a DurationHistogram<N>);
+
+impl<const N: usize> Serialize for Buckets<'_, N> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut seq = serializer.serialize_seq(None)?;
+		for (i, &n) in self.0.counts.iter().enumerate() {
+			if n != 0 {
+				seq.serialize_element(&(i, n))?;
+			}
+		}
+		seq.end()
+	}
+}
+
+impl<const N: usize> Serialize for DurationHistogram<N> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut s = serializer.serialize_struct("DurationHistogram", 4)?;
+		s.serialize_field("significant_digits", &self.digits)?;
+		s.serialize_field("min", &self.min())?;
+		s.serialize_field("max", &self.max())?;
+		s.serialize_field("buckets", &Buckets(self))?;
+		s.end()
+	}
+}
+
+const HISTOGRAM_FIELDS: &[&str] = &["significant_digits", "min", "max", "buckets"];
+
+enum HistogramField {
+	SignificantDigits,
+	Min,
+	Max,
+	Buckets,
+}
+
+impl<'de> Deserialize<'de> for HistogramField {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct FieldVisitor;
+
+		impl Visitor<'_> for FieldVisitor {
+			type Value = HistogramField;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("a histogram field")
+			}
+
+			fn visit_str<E>(self, s: &str) -> Result<HistogramField, E>
+			where
+				E: de::Error,
+			{
+				match s {
+					"significant_digits" => Ok(HistogramField::SignificantDigits),
+					"min" => Ok(HistogramField::Min),
+					"max" => Ok(HistogramField::Max),
+					"buckets" => Ok(HistogramField::Buckets),
+					_ => Err(E::unknown_field(s, HISTOGRAM_FIELDS)),
+				}
+			}
+		}
+
+		deserializer.deserialize_identifier(FieldVisitor)
+	}
+}
+
+struct HistogramVisitor<const N: usize>;
+
+impl<const N: usize> HistogramVisitor<N> {
+	fn build<E: de::Error>(
+		digits: u8,
+		min: Option<Duration>,
+		max: Option<Duration>,
+		buckets: Vec<(usize, u64)>,
+	) -> Result<DurationHistogram<N>, E> {
+		if !(1..=5).contains(&digits) {
+			return Err(E::invalid_value(
+				de::Unexpected::Unsigned(digits as u64),
+				&"a number of significant digits between 1 and 5",
+			));
+		}
+		if N < 1 << histogram::sub_bits(digits) {
+			return Err(E::custom(
+				"the histogram capacity is too small for this many significant digits",
+			));
+		}
+
+		let mut h = DurationHistogram::<N>::new(digits);
+		for (i, n) in buckets {
+			if i >= N {
+				return Err(E::custom(format_args!(
+					"bucket index {i} is out of bounds for a histogram with {N} buckets"
+				)));
+			}
+			if n == 0 {
+				continue;
+			}
+			// Listing a bucket twice would make the count differ from the
+			// bucket total.
+			if h.counts[i] != 0 {
+				return Err(E::custom(format_args!(
+					"bucket index {i} appears more than once"
+				)));
+			}
+			h.counts[i] = n;
+			h.count = h.count.saturating_add(n);
+		}
+
+		let mismatch = || E::custom("the histogram min and max don't match its buckets");
+		match (min, max) {
+			(Some(min), Some(max)) if h.count != 0 => {
+				let first = h.counts.iter().position(|&n| n != 0).unwrap_or(0);
+				let last = h.counts.iter().rposition(|&n| n != 0).unwrap_or(0);
+				if min > max || h.index(min.0) < first || h.index(max.0) > last {
+					return Err(mismatch());
+				}
+				h.min = min.0;
+				h.max = max.0;
+			}
+			(None, None) if h.count == 0 => (),
+			_ => return Err(mismatch()),
+		}
+
+		Ok(h)
+	}
+}
+
+impl<'de, const N: usize> Visitor<'de> for HistogramVisitor<N> {
+	type Value = DurationHistogram<N>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a duration histogram")
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: de::SeqAccess<'de>,
+	{
+		let digits = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		let min = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+		let max = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+		let buckets = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+		Self::build(digits, min, max, buckets)
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: de::MapAccess<'de>,
+	{
+		let mut digits = None;
+		let mut min = None;
+		let mut max = None;
+		let mut buckets = None;
+
+		while let Some(key) = map.next_key()? {
+			match key {
+				HistogramField::SignificantDigits => digits = Some(map.next_value()?),
+				HistogramField::Min => min = Some(map.next_value()?),
+				HistogramField::Max => max = Some(map.next_value()?),
+				HistogramField::Buckets => buckets = Some(map.next_value()?),
+			}
+		}
+
+		Self::build(
+			digits.ok_or_else(|| de::Error::missing_field("significant_digits"))?,
+			min.ok_or_else(|| de::Error::missing_field("min"))?,
+			max.ok_or_else(|| de::Error::missing_field("max"))?,
+			buckets.ok_or_else(|| de::Error::missing_field("buckets"))?,
+		)
+	}
+}
+
+impl<'de, const N: usize> Deserialize<'de> for DurationHistogram<N> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_struct("DurationHistogram", HISTOGRAM_FIELDS, HistogramVisitor)
+	}
+}
@@ -0,0 +1,188 @@
+use crate::*;
+
+// One significant digit keeps percentiles within about 3% of the true value
+// while keeping the histogram small.
+const DIGITS: u8 = 1;
+const CAPACITY: usize = DurationHistogram::<0>::capacity_for(DIGITS, Duration(u64::MAX as u128));
+
+/// Accumulates summary statistics over durations, such as timings from a
+/// benchmark.
+///
+/// Tracks the count, minimum, maximum, mean and variance exactly (the mean and
+/// variance up to floating point precision) and approximates percentiles with a
+/// fixed-size [DurationHistogram], so it doesn't need to store the recorded
+/// values or allocate.
+/// Percentiles are accurate to within about 3% of the true value.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, DurationStats};
+///
+/// let stats = (1..=100).map(Duration::from_millis).collect::<DurationStats>();
+///
+/// assert_eq!(stats.count(), 100);
+/// assert_eq!(stats.min(), Some(Duration::from_millis(1)));
+/// assert_eq!(stats.max(), Some(Duration::from_millis(100)));
+/// assert_eq!(stats.mean(), Some(Duration::from_micros(50_500)));
+///
+/// let p50 = stats.p50().unwrap();
+/// assert!(p50 > Duration::from_millis(48) && p50 < Duration::from_millis(52));
+/// println!("median: {p50}");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DurationStats {
+	total: u128,
+	mean: f64,
+	m2: f64,
+	histogram: DurationHistogram<CAPACITY>,
+}
+
+impl Default for DurationStats {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl DurationStats {
+	/// Creates an empty accumulator.
+	pub const fn new() -> Self {
+		Self {
+			total: 0,
+			mean: 0.0,
+			m2: 0.0,
+			histogram: DurationHistogram::new(DIGITS),
+		}
+	}
+
+	/// Records a duration.
+	pub fn record(&mut self, d: impl Into<Duration>) {
+		let d = d.into();
+		self.histogram.record(d);
+		self.total = self.total.saturating_add(d.0);
+
+		// Welford's online algorithm.
+		let x = d.0 as f64;
+		let delta = x - self.mean;
+		self.mean += delta / self.count() as f64;
+		self.m2 += delta * (x - self.mean);
+	}
+
+	/// Returns the number of recorded durations.
+	pub const fn count(&self) -> u64 {
+		self.histogram.count()
+	}
+
+	/// Returns the sum of all the recorded durations.
+	///
+	/// Saturates at the maximum representable duration.
+	pub const fn total(&self) -> Duration {
+		Duration(self.total)
+	}
+
+	/// Returns the smallest recorded duration, or [None] if nothing has been
+	/// recorded.
+	pub const fn min(&self) -> Option<Duration> {
+		self.histogram.min()
+	}
+
+	/// Returns the largest recorded duration, or [None] if nothing has been
+	/// recorded.
+	pub const fn max(&self) -> Option<Duration> {
+		self.histogram.max()
+	}
+
+	/// Returns the arithmetic mean of the recorded durations, or [None] if
+	/// nothing has been recorded.
+	pub fn mean(&self) -> Option<Duration> {
+		match self.count() {
+			0 => None,
+			_ if self.total == u128::MAX => Some(Duration(self.mean as u128)),
+			n => Some(Duration(self.total / n as u128)),
+		}
+	}
+
+	/// Returns the population variance of the recorded durations in
+	/// nanoseconds squared, or [None] if nothing has been recorded.
+	pub fn variance(&self) -> Option<f64> {
+		match self.count() {
+			0 => None,
+			n => Some(self.m2 / n as f64),
+		}
+	}
+
+	/// Returns the population standard deviation of the recorded durations,
+	/// or [None] if nothing has been recorded.
+	pub fn stddev(&self) -> Option<Duration> {
+		self.variance().map(|v| Duration((v as u128).isqrt()))
+	}
+
+	/// Returns an approximation of the `p`th percentile of the recorded
+	/// durations, or [None] if nothing has been recorded.
+	///
+	/// `p` is clamped into the range `0.0..=100.0`.
+	/// The result is always between [min](Self::min) and [max](Self::max).
+	pub fn percentile(&self, p: f64) -> Option<Duration> {
+		self.histogram.quantile(p / 100.0)
+	}
+
+	/// Returns an approximation of the median.
+	pub fn p50(&self) -> Option<Duration> {
+		self.percentile(50.0)
+	}
+
+	/// Returns an approximation of the 90th percentile.
+	pub fn p90(&self) -> Option<Duration> {
+		self.percentile(90.0)
+	}
+
+	/// Returns an approximation of the 99th percentile.
+	pub fn p99(&self) -> Option<Duration> {
+		self.percentile(99.0)
+	}
+}
+
+impl<T: Into<Duration>> Extend<T> for DurationStats {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		for d in iter {
+			self.record(d);
+		}
+	}
+}
+
+impl<T: Into<Duration>> FromIterator<T> for DurationStats {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let mut stats = Self::new();
+		stats.extend(iter);
+		stats
+	}
+}
+
+impl Display for DurationStats {
+	/// Prints a one-line summary, e.g.
+	/// `n=3 min=1ms mean=2ms max=3ms stddev=816.49us p50=2ms p90=3ms p99=3ms`.
+	///
+	/// The formatting flags are forwarded to each [Duration].
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "n={}", self.count())?;
+		if self.count() == 0 {
+			return Ok(());
+		}
+
+		let stats = [
+			("min", self.min()),
+			("mean", self.mean()),
+			("max", self.max()),
+			("stddev", self.stddev()),
+			("p50", self.p50()),
+			("p90", self.p90()),
+			("p99", self.p99()),
+		];
+
+		for (name, d) in stats {
+			write!(f, " {name}=")?;
+			Display::fmt(&d.unwrap_or_default(), f)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,297 @@
+use crate::*;
+
+/// systemd's `USEC_INFINITY`, the largest time span in microseconds.
+const USEC_INFINITY: u128 = u64::MAX as u128;
+
+const USEC_PER_MSEC: u128 = 1_000;
+const USEC_PER_SEC: u128 = 1_000_000;
+const USEC_PER_MINUTE: u128 = 60 * USEC_PER_SEC;
+const USEC_PER_HOUR: u128 = 60 * USEC_PER_MINUTE;
+const USEC_PER_DAY: u128 = 24 * USEC_PER_HOUR;
+const USEC_PER_WEEK: u128 = 7 * USEC_PER_DAY;
+/// 30.44 days.
+const USEC_PER_MONTH: u128 = 2_629_800 * USEC_PER_SEC;
+/// 365.25 days.
+const USEC_PER_YEAR: u128 = 31_557_600 * USEC_PER_SEC;
+
+/// The units `parse_time` understands, in the order it tries them.
+///
+/// They're case-sensitive: `m` is a minute and `M` is a month.
+const UNITS: [(&str, u128); 30] = [
+	("seconds", USEC_PER_SEC),
+	("second", USEC_PER_SEC),
+	("sec", USEC_PER_SEC),
+	("s", USEC_PER_SEC),
+	("minutes", USEC_PER_MINUTE),
+	("minute", USEC_PER_MINUTE),
+	("min", USEC_PER_MINUTE),
+	("months", USEC_PER_MONTH),
+	("month", USEC_PER_MONTH),
+	("msec", USEC_PER_MSEC),
+	("ms", USEC_PER_MSEC),
+	("m", USEC_PER_MINUTE),
+	("hours", USEC_PER_HOUR),
+	("hour", USEC_PER_HOUR),
+	("hr", USEC_PER_HOUR),
+	("h", USEC_PER_HOUR),
+	("days", USEC_PER_DAY),
+	("day", USEC_PER_DAY),
+	("d", USEC_PER_DAY),
+	("weeks", USEC_PER_WEEK),
+	("week", USEC_PER_WEEK),
+	("w", USEC_PER_WEEK),
+	("years", USEC_PER_YEAR),
+	("year", USEC_PER_YEAR),
+	("y", USEC_PER_YEAR),
+	("usec", 1),
+	("us", 1),
+	("μs", 1),
+	("µs", 1),
+	("M", USEC_PER_MONTH),
+];
+
+/// The units `format_timespan` writes, from the largest.
+const FORMAT_UNITS: [(&str, u128); 9] = [
+	("y", USEC_PER_YEAR),
+	("month", USEC_PER_MONTH),
+	("w", USEC_PER_WEEK),
+	("d", USEC_PER_DAY),
+	("h", USEC_PER_HOUR),
+	("min", USEC_PER_MINUTE),
+	("s", USEC_PER_SEC),
+	("ms", USEC_PER_MSEC),
+	("us", 1),
+];
+
+/// Implements [Display] in the time span format of systemd, as described in
+/// `systemd.time(7)`.
+///
+/// The output matches systemd's `format_timespan`, which `systemctl show`
+/// uses, such as `1y 2month 3w 4d 5h 6min 7s`, `1min 30.500000s` or `0`:
+/// a month is 30.44 days, a year is 365.25 days and durations shorter than a
+/// minute are written with a fraction.
+///
+/// systemd counts microseconds, so shorter durations are truncated.
+/// [Duration::MAX] and durations too long for systemd are written as
+/// `infinity`.
+///
+/// The only way to obtain a value of this struct is via the [format_systemd](Duration::format_systemd) method on [Duration].
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(Duration::from_secs(9000).format_systemd().to_string(), "2h 30min");
+/// assert_eq!(Duration::from_millis(1500).format_systemd().to_string(), "1.500000s");
+/// assert_eq!(Duration::MAX.format_systemd().to_string(), "infinity");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SystemdDisplay(pub(crate) u128);
+
+impl Display for SystemdDisplay {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut t = self.0 / MICROSECOND;
+		if t >= USEC_INFINITY {
+			return f.write_str("infinity");
+		}
+		if t == 0 {
+			return f.write_str("0");
+		}
+
+		let mut first = true;
+		for (suffix, unit) in FORMAT_UNITS {
+			if t == 0 {
+				break;
+			}
+			if t < unit {
+				continue;
+			}
+			if !first {
+				f.write_str(" ")?;
+			}
+			first = false;
+
+			let (a, b) = (t / unit, t % unit);
+			// The last unit below a minute has a fraction, such as `1.500000s`.
+			let digits = unit.ilog10() as usize;
+			if t < USEC_PER_MINUTE && b > 0 && digits > 0 {
+				write!(f, "{a}.{b:0digits$}{suffix}")?;
+				break;
+			}
+			write!(f, "{a}{suffix}")?;
+			t = b;
+		}
+		Ok(())
+	}
+}
+
+/// Parses a time span in the format of systemd, as described in
+/// `systemd.time(7)`.
+///
+/// This follows systemd's own parser: a time span is a sequence of numbers
+/// with optional fractions and units, such as `2h 30min`, `1y 2w`, `5usec` or
+/// `55s500ms`, or `infinity`, which is [Duration::MAX].
+/// The spaces between the parts are optional and a number without a unit is
+/// in seconds.
+/// The units are case-sensitive:
+///
+/// - `usec`, `us`, `μs`
+/// - `msec`, `ms`
+/// - `seconds`, `second`, `sec`, `s`
+/// - `minutes`, `minute`, `min`, `m`
+/// - `hours`, `hour`, `hr`, `h`
+/// - `days`, `day`, `d`
+/// - `weeks`, `week`, `w`
+/// - `months`, `month`, `M`, which are 30.44 days
+/// - `years`, `year`, `y`, which are 365.25 days
+///
+/// Like in systemd, fractions of a microsecond are truncated.
+///
+/// #### Errors
+/// Returns an error if the input isn't a valid time span or if it's too long
+/// for systemd, which stores time spans as 64-bit microseconds.
+///
+/// #### Examples
+/// ```rust
+/// use dur::Duration;
+///
+/// assert_eq!(dur::parse_systemd("2h 30min"), Ok(Duration::from_secs(9000)));
+/// assert_eq!(dur::parse_systemd("1month"), Ok(Duration::from_secs(2_629_800)));
+/// assert_eq!(dur::parse_systemd("5usec"), Ok(Duration::from_micros(5)));
+/// assert_eq!(dur::parse_systemd("90"), Ok(Duration::from_secs(90)));
+/// assert_eq!(dur::parse_systemd("infinity"), Ok(Duration::MAX));
+/// ```
+pub fn parse_systemd(input: &str) -> Result<Duration, Error> {
+	parse_systemd_spanned(input).map_err(|e| e.with_input(input))
+}
+
+fn is_whitespace(c: &u8) -> bool {
+	matches!(c, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn skip_whitespace(s: &[u8], i: usize) -> usize {
+	i + s[i..].iter().take_while(|c| is_whitespace(c)).count()
+}
+
+fn digits(s: &[u8], i: usize) -> usize {
+	i + s[i..].iter().take_while(|c| c.is_ascii_digit()).count()
+}
+
+fn parse_systemd_spanned(input: &str) -> Result<Duration, Error> {
+	let s = input.as_bytes();
+	let mut i = skip_whitespace(s, 0);
+
+	if input[i..].starts_with("infinity") {
+		let end = skip_whitespace(s, i + "infinity".len());
+		if end != s.len() {
+			return Err(Error::new(ErrorKind::InvalidDuration, end..s.len()));
+		}
+		return Ok(Duration::MAX);
+	}
+
+	let mut usecs = 0_u128;
+	let mut something = false;
+	loop {
+		i = skip_whitespace(s, i);
+		if i == s.len() {
+			if !something {
+				return Err(Error::new(ErrorKind::InvalidDuration, 0..input.len()));
+			}
+			break;
+		}
+		let start = i;
+
+		// [+-]?[0-9]*(\.[0-9]+)?
+		let neg = s[i] == b'-';
+		if neg || s[i] == b'+' {
+			i += 1;
+		}
+		let int = i..digits(s, i);
+		i = int.end;
+		let frac = match s.get(i) {
+			Some(b'.') => {
+				let end = digits(s, i + 1);
+				// `3.`, `3.sec` and `3.-1` aren't allowed.
+				if end == i + 1 {
+					return Err(Error::new(ErrorKind::InvalidDuration, start..end));
+				}
+				i = end;
+				int.end + 1..end
+			}
+			_ if int.is_empty() => {
+				let len = input[i..].chars().next().map_or(0, char::len_utf8);
+				return Err(Error::new(ErrorKind::InvalidDuration, start..i + len));
+			}
+			_ => i..i,
+		};
+		let number = start..i;
+		if neg {
+			let n = parse_dec(&input[number.clone()])
+				.map_err(|kind| Error::new(kind, number.clone()))?;
+			return Err(Error::new(ErrorKind::IsNegative(n), number));
+		}
+
+		// The unit, or seconds if there isn't one.
+		let j = skip_whitespace(s, i);
+		let unit = UNITS
+			.iter()
+			.find(|(u, _)| s[j..].starts_with(u.as_bytes()))
+			.map(|&(u, n)| (u.len(), n));
+		let multiplier = match unit {
+			Some((len, n)) => {
+				i = j + len;
+				n
+			}
+			// The next part follows, possibly without a unit either.
+			None if j == s.len()
+				|| (j != i && matches!(s[j], b'0'..=b'9' | b'.' | b'+' | b'-')) =>
+			{
+				USEC_PER_SEC
+			}
+			// `12.34.56` isn't allowed.
+			None => {
+				let word = input[j..]
+					.find(|c: char| !c.is_alphabetic())
+					.unwrap_or(input.len() - j);
+				let (kind, len) = match word {
+					0 => (
+						ErrorKind::InvalidDuration,
+						input[j..].chars().next().map_or(0, char::len_utf8),
+					),
+					n => (ErrorKind::InvalidUnit, n),
+				};
+				return Err(Error::new(kind, j..j + len));
+			}
+		};
+
+		let too_big = Error::new(ErrorKind::ValueTooBig, start..i);
+		// The integer part is a C `long long`, and `.5` is fine.
+		let int = match &input[int] {
+			"" => 0,
+			n => n
+				.parse::<u128>()
+				.ok()
+				.filter(|&n| n <= i64::MAX as u128)
+				.ok_or(too_big.clone())?,
+		};
+		if int >= USEC_INFINITY / multiplier {
+			return Err(too_big);
+		}
+		usecs += int * multiplier;
+		if usecs >= USEC_INFINITY {
+			return Err(too_big);
+		}
+
+		let mut m = multiplier / 10;
+		for &c in &s[frac] {
+			usecs += u128::from(c - b'0') * m;
+			if usecs >= USEC_INFINITY {
+				return Err(too_big);
+			}
+			m /= 10;
+		}
+		something = true;
+	}
+
+	Ok(Duration(usecs * MICROSECOND))
+}
@@ -1,6 +1,3 @@
-#![cfg(not(feature = "alloc"))]
-compile_error!("you need to enable the alloc feature to run tests");
-
 use alloc::{
 	format,
 	string::ToString,
@@ -44,6 +41,1524 @@ fn parse() {
 	}
 }
 
+#[test]
+fn unit() {
+	for unit in Unit::ALL {
+		assert_eq!(unit.short_name().parse::<Unit>(), Ok(unit));
+		assert_eq!(unit.long_name().parse::<Unit>(), Ok(unit));
+		for alias in unit.aliases() {
+			assert_eq!(alias.to_ascii_uppercase().parse::<Unit>(), Ok(unit));
+			assert_eq!(
+				crate::parse(&format!("2{alias}")),
+				Ok(Duration(2 * unit.nanos()))
+			);
+		}
+
+		assert_eq!(
+			Duration::from_unit(Decimal::ONE, unit),
+			Duration(unit.nanos())
+		);
+		assert_eq!(Duration(unit.nanos() * 3).in_whole(unit), 3);
+		assert_eq!(Duration(unit.nanos() * 3).as_unit(unit), Decimal::from(3));
+	}
+
+	assert_eq!(" mins ".parse::<Unit>(), Ok(Unit::Minute));
+	assert_eq!(
+		"".parse::<Unit>().map_err(|e| e.kind()),
+		Err(ErrorKind::MissingUnit)
+	);
+	let e = " minz".parse::<Unit>().unwrap_err();
+	assert_eq!(e.kind(), ErrorKind::InvalidUnit);
+	assert_eq!(e.span(), 1..5);
+	assert_eq!(e.suggestion(), Some("mins"));
+	assert!("m s".parse::<Unit>().is_err());
+	assert_eq!(Unit::Year.to_string(), "yr");
+	assert_eq!(format!("{:#}", Unit::Year), "year");
+
+	// Longest match wins: `ms` isn't `m` followed by `s`.
+	assert_eq!(crate::parse("1ms"), Ok(Duration(MILLISECOND)));
+	assert_eq!(crate::parse("1mins"), Ok(Duration(MINUTE)));
+	assert!(crate::parse("1mons").is_err());
+
+	assert_eq!(
+		Duration::checked_from_unit(Decimal::new(25, 1), Unit::Second),
+		Some(Duration(2500 * MILLISECOND))
+	);
+	assert_eq!(
+		Duration::checked_from_unit(Decimal::new(15, 10), Unit::Nanosecond),
+		Some(Duration(0))
+	);
+	assert_eq!(
+		Duration::checked_from_unit(-Decimal::ONE, Unit::Second),
+		None
+	);
+	assert_eq!(Duration::checked_from_unit(Decimal::MAX, Unit::Year), None);
+	assert_eq!(Duration(u128::MAX).as_unit(Unit::Nanosecond), Decimal::MAX);
+	assert_eq!(Duration(1).as_unit(Unit::Microsecond), Decimal::new(1, 3));
+}
+
+#[test]
+fn parse_const() {
+	let mut inputs = alloc::vec![
+		"5",
+		"1_000",
+		"+5",
+		"-0",
+		"-5",
+		"-5.5",
+		"1.",
+		".5",
+		".",
+		"_1",
+		" 5",
+		"5 ",
+		"5\n",
+		"",
+		" ",
+		"5m 2 seconds",
+		"52 day 2nanoseconds",
+		"1h1m1s",
+		"0.1 s 0.1 sec 0.1secs",
+		"2\tweek",
+		"5. yrs .5h",
+		"1.5ns",
+		"5m,3s",
+		"5m, 3s",
+		"5m ,3s",
+		"5m  3s",
+		"1,",
+		"5m,",
+		"+5m",
+		"-0m",
+		"-3m",
+		"5m -3s",
+		"1mons",
+		"1ms",
+		"1 parsec",
+		"1.9999999999999999999999999999999ns",
+		"0.0000000000000000000000000000001s",
+		"100000000000000000000000000000s",
+		"79228162514264337593543950335ns",
+		"79228162514264337593543950336ns",
+		"79228162514264337593543950335",
+		"2000000000000y",
+		"1y 1y 1y",
+		"3.1415926535897932384626433832 w",
+		"123456789.123456789 d",
+		"1e3",
+		"1E3",
+		"1.5e-3",
+		"-1e3",
+		"1e",
+		"1e3ms",
+		"1e+3",
+		"1e-+3",
+		"1e-",
+		"1e3 ",
+		"1_000e-3",
+		"1.5e30",
+		"5e28",
+		"1e-30",
+		"123.456e-27",
+		"0.00000000000000000000000000015e-1",
+		"1.2345e-26",
+		"-1.2345e-26",
+		"-0.00000000000000000000000000015e-1",
+		"1.23456789e-3",
+		"1e4294967296",
+	];
+	let aliases = Unit::ALL
+		.iter()
+		.flat_map(|u| u.aliases())
+		.map(|a| format!("1.5{a}"))
+		.collect::<alloc::vec::Vec<_>>();
+	inputs.extend(aliases.iter().map(|s| s.as_str()));
+
+	for s in inputs {
+		// `parse_const` doesn't store the input or suggest units.
+		let key = |e: Error| (e.kind(), e.span());
+		assert_eq!(
+			crate::parse_const(s).map_err(key),
+			crate::parse(s).map_err(key),
+			"{s:?}"
+		);
+	}
+
+	const D: Duration = dur!("1h 30m");
+	assert_eq!(D, Duration(HOUR + 30 * MINUTE));
+	assert_eq!(dur!("250"), Duration(250 * MILLISECOND));
+}
+
+#[test]
+fn custom_units() {
+	const FRAME: CustomUnit = CustomUnit::from_ratio("frame", &["fr", "f"], SECOND, 60);
+	const PARSER: Parser = Parser::with_units(&[
+		CustomUnit::new("tick", &[], 50 * MILLISECOND),
+		FRAME,
+		CustomUnit::new("sprint", &["sp"], 2 * WEEK),
+		CustomUnit::new("shift", &[], 8 * HOUR),
+	]);
+
+	let tests = [
+		("1 tick", 50 * MILLISECOND),
+		("3TICKS", 150 * MILLISECOND),
+		("1 frame", SECOND / 60),
+		("2 frames", 2 * SECOND / 60),
+		("90f", 1500 * MILLISECOND),
+		("1.5 fr", SECOND / 40),
+		("1sp 2d", 16 * DAY),
+		("2 sprints, 1 shift", 4 * WEEK + 8 * HOUR),
+		("1 shift 30m", 8 * HOUR + 30 * MINUTE),
+		("1h 1ms", HOUR + MILLISECOND),
+		("7", 7 * MILLISECOND),
+	];
+	for (s, ns) in tests {
+		assert_eq!(PARSER.parse(s), Ok(Duration(ns)), "{s:?}");
+	}
+
+	assert!(PARSER.parse("1 ticktock").is_err());
+	assert!(crate::parse("1 tick").is_err());
+	assert_eq!(Parser::new().parse("5m 2s"), crate::parse("5m 2s"));
+
+	let tests = [
+		(Duration(0), "0ns"),
+		(Duration(3 * MILLISECOND), "3ms"),
+		(Duration(150 * MILLISECOND), "3 ticks"),
+		(Duration(160 * MILLISECOND), "3 ticks 10ms"),
+		(Duration(2 * SECOND / 60), "2 frames"),
+		(Duration(59 * SECOND / 60), "19 ticks 2 frames"),
+		(Duration(SECOND), "1s"),
+		(Duration(10 * HOUR), "1 shift 2h"),
+		(Duration(4 * WEEK + 8 * HOUR), "2 sprints 1 shift"),
+		(Duration(YEAR), "1yr"),
+	];
+	for (d, s) in tests {
+		assert_eq!(PARSER.display(d).to_string(), s, "{d:?}");
+		let round = PARSER.parse(s).unwrap();
+		assert!(d.abs_diff(round) < Duration(SECOND), "{s:?}");
+	}
+	assert_eq!(
+		format!("{:#}", PARSER.display(Duration(10 * HOUR))),
+		"1 shift 2 hours"
+	);
+
+	assert_eq!(FRAME.name(), "frame");
+	assert_eq!(FRAME.aliases(), ["fr", "f"]);
+	assert_eq!((FRAME.numerator(), FRAME.denominator()), (SECOND, 60));
+	assert_eq!(PARSER.units().len(), 4);
+}
+
+#[test]
+#[should_panic = "collides with a built-in unit"]
+fn custom_unit_collision() {
+	// `hr` + `s` is a built-in alias.
+	static UNITS: [CustomUnit; 1] = [CustomUnit::new("hr", &[], HOUR)];
+	Parser::with_units(&UNITS);
+}
+
+#[test]
+fn natural_language() {
+	const PARSER: Parser = Parser::new().with_natural_language(true);
+
+	let tests = [
+		("an hour and a half", HOUR + 30 * MINUTE),
+		("two minutes", 2 * MINUTE),
+		("a couple of seconds", 2 * SECOND),
+		("half an hour", 30 * MINUTE),
+		("1 hour and 30 minutes", HOUR + 30 * MINUTE),
+		("Two Hours And A Half", 2 * HOUR + 30 * MINUTE),
+		("a quarter of an hour", 15 * MINUTE),
+		("three quarters of an hour", 45 * MINUTE),
+		("two and a half days", 2 * DAY + 12 * HOUR),
+		("an hour and a half minute", HOUR + 30 * SECOND),
+		("a week, two days and an hour", WEEK + 2 * DAY + HOUR),
+		("one hundred and twenty-five seconds", 125 * SECOND),
+		("twenty one minutes", 21 * MINUTE),
+		("a thousand years", 1000 * YEAR),
+		("five thousand two hundred and six ms", 5206 * MILLISECOND),
+	];
+	for (s, expected) in tests {
+		assert_eq!(PARSER.parse(s), Ok(Duration(expected)), "{s:?}");
+	}
+
+	// Everything `parse` accepts is parsed the same way.
+	for s in [
+		"1h 30m",
+		"1h30m",
+		"42",
+		"1.5 hours, 2 min",
+		"3.1415926535897932384626433832 w",
+	] {
+		assert_eq!(PARSER.parse(s), crate::parse(s), "{s:?}");
+	}
+
+	let errors = [
+		("two", ErrorKind::MissingUnit, 0..3),
+		("two parsecs", ErrorKind::InvalidUnit, 4..11),
+		("an hour and", ErrorKind::InvalidDuration, 8..11),
+		("one two minutes", ErrorKind::InvalidUnit, 4..7),
+		("-5 minutes", ErrorKind::IsNegative(Decimal::from(-5)), 0..2),
+		(
+			"an hour and 3000000000000 years",
+			ErrorKind::ValueTooBig,
+			12..31,
+		),
+	];
+	for (s, kind, span) in errors {
+		let e = PARSER.parse(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	// It's opt-in.
+	assert!(crate::parse("two minutes").is_err());
+}
+
+#[test]
+fn parse_prefix() {
+	let tests = [
+		("5s) then wait 1m", 5 * SECOND, ") then wait 1m"),
+		("1h 30m", HOUR + 30 * MINUTE, ""),
+		("1h 30m later", HOUR + 30 * MINUTE, " later"),
+		("1h, 5 parsecs", HOUR, ", 5 parsecs"),
+		("2 minutes 3 retries", 2 * MINUTE, " 3 retries"),
+		("1.5s,x", 1500 * MILLISECOND, ",x"),
+		("1h 30", HOUR, " 30"),
+	];
+	for (s, d, rem) in tests {
+		assert_eq!(crate::parse_prefix(s), Ok((Duration(d), rem)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("3 retries", ErrorKind::InvalidUnit, 2..9),
+		("5)", ErrorKind::InvalidUnit, 1..2),
+		// A lone number isn't a duration.
+		("500", ErrorKind::MissingUnit, 0..3),
+		("1h -5m", ErrorKind::IsNegative(Decimal::new(-5, 0)), 3..5),
+		("1h 3000000000000y", ErrorKind::ValueTooBig, 3..17),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_prefix(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	const NATURAL: Parser = Parser::new().with_natural_language(true);
+	assert_eq!(
+		NATURAL.parse_prefix("an hour and a half and then some"),
+		Ok((Duration(HOUR + 30 * MINUTE), " and then some"))
+	);
+	assert_eq!(
+		NATURAL.parse_prefix("two minutes, later"),
+		Ok((Duration(2 * MINUTE), ", later"))
+	);
+}
+
+#[test]
+fn nom_combinators() {
+	use ::nom::{
+		bytes::complete::tag,
+		error::{
+			Error as NomError,
+			ErrorKind as NomErrorKind,
+		},
+		sequence::{
+			delimited,
+			separated_pair,
+		},
+		Err,
+	};
+
+	type E<'a> = NomError<&'a str>;
+
+	assert_eq!(
+		crate::nom::duration::<E>("1m 30s)"),
+		Ok((")", Duration(90 * SECOND)))
+	);
+	assert_eq!(
+		crate::nom::duration::<E>("x"),
+		Err(Err::Error(NomError::new("x", NomErrorKind::Digit)))
+	);
+	assert_eq!(
+		crate::nom::duration::<E>("1h 3000000000000y"),
+		Err(Err::Error(NomError::new(
+			"3000000000000y",
+			NomErrorKind::Verify
+		)))
+	);
+
+	assert_eq!(crate::nom::unit::<E>("min 2"), Ok((" 2", Unit::Minute)));
+	assert_eq!(
+		crate::nom::unit::<E>("mint"),
+		Err(Err::Error(NomError::new("mint", NomErrorKind::Tag)))
+	);
+
+	assert_eq!(
+		crate::nom::number::<E>("-1.5s"),
+		Ok(("s", Decimal::new(-15, 1)))
+	);
+	assert_eq!(
+		crate::nom::number::<E>("s"),
+		Err(Err::Error(NomError::new("s", NomErrorKind::Digit)))
+	);
+
+	// `[1s; 2m]`
+	let mut pair = delimited(
+		tag::<_, _, E>("["),
+		separated_pair(crate::nom::duration, tag("; "), crate::nom::duration),
+		tag("]"),
+	);
+	assert_eq!(
+		pair("[1s; 2m]"),
+		Ok(("", (Duration(SECOND), Duration(2 * MINUTE))))
+	);
+}
+
+#[test]
+fn go() {
+	// From Go's `TestDurationString`.
+	let tests = [
+		("0s", 0),
+		("1ns", 1),
+		("1.1µs", 1100),
+		("2.2ms", 2200 * MICROSECOND),
+		("3.3s", 3300 * MILLISECOND),
+		("4m5s", 4 * MINUTE + 5 * SECOND),
+		("4m5.001s", 4 * MINUTE + 5001 * MILLISECOND),
+		("5h6m7.001s", 5 * HOUR + 6 * MINUTE + 7001 * MILLISECOND),
+		("8m0.000000001s", 8 * MINUTE + 1),
+		("2562047h47m16.854775807s", i64::MAX as u128),
+		("1h0m0s", HOUR),
+		("48h0m0s", 2 * DAY),
+		("1m0s", MINUTE),
+		("999ns", 999),
+		("1µs", MICROSECOND),
+		("999.999µs", 999_999),
+		("1ms", MILLISECOND),
+		("999.999999ms", SECOND - 1),
+		("1s", SECOND),
+	];
+	for (s, n) in tests {
+		assert_eq!(Duration(n).format_go().to_string(), s, "{n}");
+		assert_eq!(crate::parse_go(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	// From Go's `TestParseDuration`.
+	let tests = [
+		("0", 0),
+		("5s", 5 * SECOND),
+		("30s", 30 * SECOND),
+		("1478s", 1478 * SECOND),
+		("+5s", 5 * SECOND),
+		("-0", 0),
+		("+0", 0),
+		("5.0s", 5 * SECOND),
+		("5.6s", 5600 * MILLISECOND),
+		("5.s", 5 * SECOND),
+		(".5s", 500 * MILLISECOND),
+		("1.0s", SECOND),
+		("1.00s", SECOND),
+		("1.004s", 1004 * MILLISECOND),
+		("1.0040s", 1004 * MILLISECOND),
+		("100.00100s", 100_001 * MILLISECOND),
+		("10ns", 10),
+		("11us", 11 * MICROSECOND),
+		("12µs", 12 * MICROSECOND),
+		("12μs", 12 * MICROSECOND),
+		("13ms", 13 * MILLISECOND),
+		("14s", 14 * SECOND),
+		("15m", 15 * MINUTE),
+		("16h", 16 * HOUR),
+		("3h30m", 3 * HOUR + 30 * MINUTE),
+		("10.5s4m", 4 * MINUTE + 10_500 * MILLISECOND),
+		(
+			"1h2m3s4ms5us6ns",
+			HOUR + 2 * MINUTE + 3 * SECOND + 4_005_006,
+		),
+		(
+			"39h9m14.425s",
+			39 * HOUR + 9 * MINUTE + 14_425 * MILLISECOND,
+		),
+		("52763797000ns", 52_763_797_000),
+		("0.3333333333333333333h", 20 * MINUTE),
+		("9007199254740993ns", (1 << 53) + 1),
+		("9223372036854775807ns", i64::MAX as u128),
+		("9223372036854775.807us", i64::MAX as u128),
+		("9223372036s854ms775us807ns", i64::MAX as u128),
+		("0.100000000000000000000h", 6 * MINUTE),
+		(
+			"0.830103483285477580700h",
+			49 * MINUTE + 48 * SECOND + 372_539_827,
+		),
+	];
+	for (s, n) in tests {
+		assert_eq!(crate::parse_go(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("3", ErrorKind::MissingUnit, 0..1),
+		("-", ErrorKind::InvalidDuration, 0..1),
+		("s", ErrorKind::InvalidDuration, 0..1),
+		(".", ErrorKind::InvalidDuration, 0..1),
+		("-.", ErrorKind::InvalidDuration, 1..2),
+		(".s", ErrorKind::InvalidDuration, 0..1),
+		("+.s", ErrorKind::InvalidDuration, 1..2),
+		("1d", ErrorKind::InvalidUnit, 1..2),
+		("1h 2m", ErrorKind::InvalidUnit, 1..3),
+		("1H", ErrorKind::InvalidUnit, 1..2),
+		("hello world", ErrorKind::InvalidDuration, 0..1),
+		("9223372036854775808ns", ErrorKind::ValueTooBig, 0..21),
+		("9223372036854775.808us", ErrorKind::ValueTooBig, 0..22),
+		("9223372036854ms775us808ns", ErrorKind::ValueTooBig, 0..25),
+		("-9223372036854775809ns", ErrorKind::ValueTooBig, 1..20),
+		("99999999999999999999s", ErrorKind::ValueTooBig, 0..20),
+		(
+			"-5s",
+			ErrorKind::IsNegative(Decimal::new(-5_000_000_000, 0)),
+			0..3,
+		),
+		(
+			"-9223372036854775808ns",
+			ErrorKind::IsNegative(-Decimal::from(1_u128 << 63)),
+			0..22,
+		),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_go(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	// Longer durations than Go supports still format the same way.
+	assert_eq!(
+		Duration(u64::MAX as u128).format_go().to_string(),
+		"5124095h34m33.709551615s"
+	);
+}
+
+#[test]
+fn systemd() {
+	const MONTH: u128 = 2_629_800 * SECOND;
+
+	// Mostly from systemd's `test_parse_sec`.
+	let tests = [
+		("5s", 5 * SECOND),
+		("5s500ms", 5500 * MILLISECOND),
+		(" 5.5s 0.5ms ", 5500 * MILLISECOND + 500 * MICROSECOND),
+		(" .22s ", 220 * MILLISECOND),
+		(" .50y ", YEAR / 2),
+		("2.5", 2500 * MILLISECOND),
+		(".7", 700 * MILLISECOND),
+		("23us", 23 * MICROSECOND),
+		("23μs", 23 * MICROSECOND),
+		("23µs", 23 * MICROSECOND),
+		("+3.1s", 3100 * MILLISECOND),
+		("3.1s.2", 3300 * MILLISECOND),
+		("3.1 .2", 3300 * MILLISECOND),
+		("3.1 sec .2 sec", 3300 * MILLISECOND),
+		("3.1 sec 1.2 sec", 4300 * MILLISECOND),
+		("2h 30min", 2 * HOUR + 30 * MINUTE),
+		("1month", MONTH),
+		("2M", 2 * MONTH),
+		("2m", 2 * MINUTE),
+		("5usec", 5 * MICROSECOND),
+		("1y 2w", YEAR + 2 * WEEK),
+		("1h30", HOUR + 30 * SECOND),
+		("1.0000005s", SECOND),
+		("infinity", u128::MAX),
+		(" infinity ", u128::MAX),
+	];
+	for (s, n) in tests {
+		assert_eq!(crate::parse_systemd(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		(" xyz ", ErrorKind::InvalidDuration, 1..2),
+		("", ErrorKind::InvalidDuration, 0..0),
+		(" . ", ErrorKind::InvalidDuration, 1..2),
+		(" 5. ", ErrorKind::InvalidDuration, 1..3),
+		(".s ", ErrorKind::InvalidDuration, 0..1),
+		("-5s ", ErrorKind::IsNegative(Decimal::new(-5, 0)), 0..2),
+		("-0.3s ", ErrorKind::IsNegative(Decimal::new(-3, 1)), 0..4),
+		("0.-0s ", ErrorKind::InvalidDuration, 0..2),
+		("3.+1s", ErrorKind::InvalidDuration, 0..2),
+		("3. 1s", ErrorKind::InvalidDuration, 0..2),
+		("3.s", ErrorKind::InvalidDuration, 0..2),
+		("12.34.56", ErrorKind::InvalidDuration, 5..6),
+		("12..34", ErrorKind::InvalidDuration, 0..3),
+		("..1234", ErrorKind::InvalidDuration, 0..1),
+		(" infinity .7", ErrorKind::InvalidDuration, 10..12),
+		(".3 infinity", ErrorKind::InvalidUnit, 3..11),
+		("5x", ErrorKind::InvalidUnit, 1..2),
+		("5 parsecs", ErrorKind::InvalidUnit, 2..9),
+		("5S", ErrorKind::InvalidUnit, 1..2),
+		("584555y", ErrorKind::ValueTooBig, 0..7),
+		("99999999999999999999s", ErrorKind::ValueTooBig, 0..21),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_systemd(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	let tests = [
+		(0, "0"),
+		(500, "0"),
+		(MICROSECOND, "1us"),
+		(1500 * MICROSECOND, "1.500ms"),
+		(100 * MILLISECOND, "100ms"),
+		(1500 * MILLISECOND, "1.500000s"),
+		(90 * SECOND, "1min 30s"),
+		(90 * SECOND + 500 * MILLISECOND, "1min 30.500000s"),
+		(2 * HOUR + 30 * MINUTE, "2h 30min"),
+		(DAY + MICROSECOND, "1d 1us"),
+		(MONTH, "1month"),
+		(YEAR, "1y"),
+		(
+			YEAR + 2 * MONTH + 3 * WEEK + 4 * DAY + 5 * HOUR + 6 * MINUTE + 7 * SECOND,
+			"1y 2month 3w 4d 5h 6min 7s",
+		),
+		(u128::MAX, "infinity"),
+		(u64::MAX as u128 * MICROSECOND, "infinity"),
+	];
+	for (n, s) in tests {
+		assert_eq!(Duration(n).format_systemd().to_string(), s, "{n}");
+	}
+
+	// Formatting and parsing round-trip at microsecond precision.
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let s = Duration(n).format_systemd().to_string();
+		let us = n - n % MICROSECOND;
+		assert_eq!(crate::parse_systemd(&s), Ok(Duration(us)), "{s:?}");
+	}
+}
+
+#[test]
+fn postgres() {
+	let interval = |months, days, microseconds| PgInterval {
+		months,
+		days,
+		microseconds,
+	};
+	let hms = (4 * 3600 + 5 * 60 + 6) * 1_000_000;
+
+	// The examples of every `IntervalStyle` from the Postgres documentation,
+	// the `postgres` style first.
+	let tests = [
+		(
+			interval(14, 0, 0),
+			["1 year 2 mons", "@ 1 year 2 mons", "1-2", "P1Y2M"],
+		),
+		(
+			interval(0, 3, hms),
+			[
+				"3 days 04:05:06",
+				"@ 3 days 4 hours 5 mins 6 secs",
+				"3 4:05:06",
+				"P3DT4H5M6S",
+			],
+		),
+		(
+			interval(-14, 3, -hms),
+			[
+				"-1 years -2 mons +3 days -04:05:06",
+				"@ 1 year 2 mons -3 days 4 hours 5 mins 6 secs ago",
+				"-1-2 +3 -4:05:06",
+				"P-1Y-2M3DT-4H-5M-6S",
+			],
+		),
+		(interval(0, 0, 0), ["00:00:00", "@ 0", "0", "PT0S"]),
+		(
+			interval(0, -1, -7_200_500_000),
+			[
+				"-1 days -02:00:00.5",
+				"@ 1 day 2 hours 0.5 secs ago",
+				"-1 2:00:00.5",
+				"P-1DT-2H-0.5S",
+			],
+		),
+		(
+			interval(0, 0, 100 * 3_600_000_000 + 1),
+			[
+				"100:00:00.000001",
+				"@ 100 hours 0.000001 secs",
+				"100:00:00.000001",
+				"PT100H0.000001S",
+			],
+		),
+	];
+	for (i, styles) in tests {
+		assert_eq!(i.to_string(), styles[0]);
+		for s in styles {
+			assert_eq!(s.parse::<PgInterval>(), Ok(i), "{s:?}");
+		}
+	}
+
+	assert_eq!("-1 years".parse::<PgInterval>(), Ok(interval(-12, 0, 0)));
+	assert_eq!(interval(1, 1, 0).to_string(), "1 mon 1 day");
+	assert_eq!(interval(0, 1, -1).to_string(), "1 day -00:00:00.000001");
+	assert_eq!(
+		interval(0, 1, -1).to_duration(),
+		Some(Duration(DAY - MICROSECOND))
+	);
+	assert_eq!(interval(0, -1, 1).to_duration(), None);
+	assert_eq!(interval(1, 0, 0).to_duration(), None);
+
+	let tests = [
+		(
+			"1 day 02:03:04.5",
+			DAY + 2 * HOUR + 3 * MINUTE + 4500 * MILLISECOND,
+		),
+		("1 day -02:00:00", 22 * HOUR),
+		("1 mon -1 mons 5 days", 5 * DAY),
+		("0.0000005 sec", 0),
+		("0.0000015 sec", 2 * MICROSECOND),
+		("P1W", WEEK),
+		("  PT1.5S  ", 1500 * MILLISECOND),
+	];
+	for (s, n) in tests {
+		assert_eq!(crate::parse_postgres(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("3 mons 2 days", ErrorKind::CalendarUnit, 0..6),
+		("2 days 1-0", ErrorKind::CalendarUnit, 7..10),
+		("-1 day", ErrorKind::IsNegative(-Decimal::from(DAY)), 0..6),
+		("5 parsecs", ErrorKind::InvalidUnit, 2..9),
+		("5.5 days", ErrorKind::InvalidDuration, 0..8),
+		("1:60:00", ErrorKind::InvalidDuration, 0..7),
+		("1:+5:00", ErrorKind::InvalidDuration, 0..7),
+		("1-12", ErrorKind::InvalidDuration, 0..4),
+		("1.5", ErrorKind::MissingUnit, 0..3),
+		("1 day ago", ErrorKind::InvalidDuration, 6..9),
+		("P", ErrorKind::InvalidDuration, 0..1),
+		("P5", ErrorKind::MissingUnit, 1..2),
+		("P1H", ErrorKind::InvalidUnit, 2..3),
+		("PT1D", ErrorKind::InvalidUnit, 3..4),
+		("P1.5D", ErrorKind::InvalidDuration, 1..5),
+		("3000000000 days", ErrorKind::ValueTooBig, 0..15),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_postgres(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	let tests = [
+		(0, "00:00:00"),
+		(500, "00:00:00"),
+		(
+			DAY + 2 * HOUR + 3 * MINUTE + 4500 * MILLISECOND,
+			"1 day 02:03:04.5",
+		),
+		(3 * DAY, "3 days"),
+		(25 * HOUR, "1 day 01:00:00"),
+		(MICROSECOND, "00:00:00.000001"),
+	];
+	for (n, s) in tests {
+		assert_eq!(Duration(n).format_postgres().to_string(), s, "{n}");
+	}
+
+	// Formatting and parsing round-trip at microsecond precision.
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let s = Duration(n).format_postgres().to_string();
+		let us = n - n % MICROSECOND;
+		assert_eq!(crate::parse_postgres(&s), Ok(Duration(us)), "{s:?}");
+	}
+}
+
+#[test]
+fn prometheus() {
+	const YEAR: u128 = 365 * DAY;
+
+	// From Prometheus' `TestParseDuration`, with the formatted output.
+	let tests = [
+		("0", 0, "0s"),
+		("0w", 0, "0s"),
+		("0s", 0, "0s"),
+		("324ms", 324 * MILLISECOND, "324ms"),
+		("3s", 3 * SECOND, "3s"),
+		("5m", 5 * MINUTE, "5m"),
+		("1h", HOUR, "1h"),
+		("4d", 4 * DAY, "4d"),
+		("4d1h", 4 * DAY + HOUR, "4d1h"),
+		("14d", 14 * DAY, "2w"),
+		("3w", 3 * WEEK, "3w"),
+		("3w2d1h", 23 * DAY + HOUR, "23d1h"),
+		("10y", 10 * YEAR, "10y"),
+		("1y1w", YEAR + WEEK, "372d"),
+		("1y1d", YEAR + DAY, "366d"),
+		("1h30m", HOUR + 30 * MINUTE, "1h30m"),
+		("1m1s1ms", MINUTE + SECOND + MILLISECOND, "1m1s1ms"),
+		(
+			"9223372036854ms",
+			9_223_372_036_854 * MILLISECOND,
+			"106751d23h47m16s854ms",
+		),
+	];
+	for (s, n, formatted) in tests {
+		assert_eq!(crate::parse_prometheus(s), Ok(Duration(n)), "{s:?}");
+		assert_eq!(
+			Duration(n).format_prometheus().to_string(),
+			formatted,
+			"{s:?}"
+		);
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("1", ErrorKind::MissingUnit, 0..1),
+		("-1s", ErrorKind::InvalidDuration, 0..1),
+		("1.5h", ErrorKind::InvalidDuration, 1..2),
+		("1h 30m", ErrorKind::InvalidDuration, 2..3),
+		(" 1h", ErrorKind::InvalidDuration, 0..1),
+		("5m4h", ErrorKind::InvalidDuration, 2..4),
+		("1h1h", ErrorKind::InvalidDuration, 2..4),
+		("1s1m", ErrorKind::InvalidDuration, 2..4),
+		("1hh", ErrorKind::InvalidUnit, 1..3),
+		("1H", ErrorKind::InvalidUnit, 1..2),
+		("1us", ErrorKind::InvalidUnit, 1..3),
+		("293y", ErrorKind::ValueTooBig, 0..4),
+		("292y25w", ErrorKind::ValueTooBig, 4..7),
+		(
+			"99999999999999999999999999999999999999999ms",
+			ErrorKind::ValueTooBig,
+			0..43,
+		),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_prometheus(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	// Formatting and parsing round-trip at millisecond precision.
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let s = Duration(n).format_prometheus().to_string();
+		let ms = n - n % MILLISECOND;
+		assert_eq!(crate::parse_prometheus(&s), Ok(Duration(ms)), "{s:?}");
+	}
+	assert_eq!(Duration(999_999).format_prometheus().to_string(), "0s");
+}
+
+#[test]
+fn humantime() {
+	use crate::parse_humantime;
+	const MONTH: u128 = 2_630_016 * SECOND;
+	const YEAR: u128 = 31_557_600 * SECOND;
+
+	// The difference table in the docs of `parse_humantime`.
+	let differences = [
+		("500", Some(500 * MILLISECOND), None),
+		("1M", Some(MINUTE), Some(MONTH)),
+		("2 months", None, Some(2 * MONTH)),
+		("1H", Some(HOUR), None),
+		("1.5h", Some(HOUR + 30 * MINUTE), None),
+		("1h,30m", Some(HOUR + 30 * MINUTE), None),
+		("1yr", Some(YEAR), None),
+		("1µs", Some(MICROSECOND), None),
+		("1 0s", None, Some(10 * SECOND)),
+	];
+	for (s, ours, theirs) in differences {
+		assert_eq!(crate::parse(s).ok(), ours.map(Duration), "{s:?}");
+		assert_eq!(parse_humantime(s).ok(), theirs.map(Duration), "{s:?}");
+	}
+	let differences = [
+		(
+			HOUR + 2 * MINUTE + 3 * SECOND + 4 * MILLISECOND,
+			"1h 2m 3s",
+			"1h 2m 3s 4ms",
+		),
+		(3 * DAY, "3d", "3days"),
+		(45 * DAY, "45d", "1month 14days 13h 26m 24s"),
+	];
+	for (n, ours, theirs) in differences {
+		assert_eq!(Duration(n).to_string(), ours);
+		assert_eq!(Duration(n).format_humantime().to_string(), theirs);
+	}
+
+	// From humantime's tests.
+	let tests = [
+		("17nsec", 17),
+		("17nanos", 17),
+		("33ns", 33),
+		("3usec", 3 * MICROSECOND),
+		("78us", 78 * MICROSECOND),
+		("31msec", 31 * MILLISECOND),
+		("31millis", 31 * MILLISECOND),
+		("6ms", 6 * MILLISECOND),
+		("3000s", 3000 * SECOND),
+		("300sec", 300 * SECOND),
+		("300secs", 300 * SECOND),
+		("50seconds", 50 * SECOND),
+		("1second", SECOND),
+		("100m", 100 * MINUTE),
+		("12min", 12 * MINUTE),
+		("12mins", 12 * MINUTE),
+		("1minute", MINUTE),
+		("7minutes", 7 * MINUTE),
+		("2h", 2 * HOUR),
+		("7hr", 7 * HOUR),
+		("7hrs", 7 * HOUR),
+		("1hour", HOUR),
+		("24hours", DAY),
+		("1day", DAY),
+		("2days", 2 * DAY),
+		("365d", 365 * DAY),
+		("1week", WEEK),
+		("7weeks", 7 * WEEK),
+		("52w", 52 * WEEK),
+		("1month", MONTH),
+		("3months", 3 * MONTH),
+		("12M", 12 * MONTH),
+		("1year", YEAR),
+		("7years", 7 * YEAR),
+		("17y", 17 * YEAR),
+		("2h 37min", 2 * HOUR + 37 * MINUTE),
+		("2h37min", 2 * HOUR + 37 * MINUTE),
+		("  1s  2ms ", SECOND + 2 * MILLISECOND),
+		("1y2M3d", YEAR + 2 * MONTH + 3 * DAY),
+		("999ms 1000ms", 1999 * MILLISECOND),
+		("18446744073709551615s", u64::MAX as u128 * SECOND),
+	];
+	for (s, n) in tests {
+		assert_eq!(parse_humantime(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("  ", ErrorKind::InvalidDuration, 0..2),
+		("1", ErrorKind::MissingUnit, 0..1),
+		("1s 2", ErrorKind::MissingUnit, 3..4),
+		("x", ErrorKind::InvalidDuration, 0..1),
+		("1s x", ErrorKind::InvalidDuration, 3..4),
+		("-1s", ErrorKind::InvalidDuration, 0..1),
+		("1.5h", ErrorKind::InvalidDuration, 1..2),
+		("1h,30m", ErrorKind::InvalidDuration, 2..3),
+		("1µs", ErrorKind::InvalidDuration, 1..3),
+		("1H", ErrorKind::InvalidUnit, 1..2),
+		("1 yr", ErrorKind::InvalidUnit, 2..4),
+		("18446744073709551616s", ErrorKind::ValueTooBig, 0..20),
+		("18446744073709551615s 1s", ErrorKind::ValueTooBig, 22..24),
+		("584942417356y", ErrorKind::ValueTooBig, 0..13),
+	];
+	for (s, kind, span) in tests {
+		let e = parse_humantime(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	assert_eq!(Duration(0).format_humantime().to_string(), "0s");
+	assert_eq!(
+		Duration(YEAR + MONTH + DAY + 1001001)
+			.format_humantime()
+			.to_string(),
+		"1year 1month 1day 1ms 1us 1ns"
+	);
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let s = Duration(n).format_humantime().to_string();
+		assert_eq!(parse_humantime(&s), Ok(Duration(n)), "{s:?}");
+	}
+}
+
+#[test]
+fn dotnet() {
+	const TICK: u128 = 100;
+	const TIMESPAN_MAX: u128 = i64::MAX as u128 * TICK;
+
+	// With the "c" and "g" formats of the same `TimeSpan` from .NET.
+	let tests = [
+		(0, "00:00:00", "0:00:00"),
+		(TICK, "00:00:00.0000001", "0:00:00.0000001"),
+		(99, "00:00:00", "0:00:00"),
+		(12 * HOUR, "12:00:00", "12:00:00"),
+		(
+			DAY + 2 * HOUR + 3 * MINUTE + 4 * SECOND + 5 * MILLISECOND,
+			"1.02:03:04.0050000",
+			"1:2:03:04.005",
+		),
+		(
+			TIMESPAN_MAX,
+			"10675199.02:48:05.4775807",
+			"10675199:2:48:05.4775807",
+		),
+	];
+	for (n, c, g) in tests {
+		assert_eq!(Duration(n).format_dotnet().to_string(), c);
+		assert_eq!(Duration(n).format_dotnet_general().to_string(), g);
+		let ticks = Duration(n - n % TICK);
+		assert_eq!(crate::parse_dotnet(c), Ok(ticks), "{c:?}");
+		assert_eq!(crate::parse_dotnet(g), Ok(ticks), "{g:?}");
+	}
+
+	let tests = [
+		("1:2:3", HOUR + 2 * MINUTE + 3 * SECOND),
+		(" 01:02:03 ", HOUR + 2 * MINUTE + 3 * SECOND),
+		("1:00:00:00.0000000", DAY),
+		("2.00:00:00.5", 2 * DAY + 500 * MILLISECOND),
+		("-00:00:00", 0),
+	];
+	for (s, n) in tests {
+		assert_eq!(crate::parse_dotnet(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("  ", ErrorKind::InvalidDuration, 0..2),
+		("1", ErrorKind::InvalidDuration, 0..1),
+		("01:30", ErrorKind::InvalidDuration, 0..5),
+		("1.2.3", ErrorKind::InvalidDuration, 0..5),
+		("1:2:3:4:5:6", ErrorKind::InvalidDuration, 0..11),
+		("001:00:00", ErrorKind::InvalidDuration, 0..3),
+		("1:02:03.", ErrorKind::InvalidDuration, 7..8),
+		("1:02:03x", ErrorKind::InvalidDuration, 7..8),
+		("1h", ErrorKind::InvalidDuration, 1..2),
+		("1.02:03:04.12345678", ErrorKind::InvalidDuration, 11..19),
+		("24:00:00", ErrorKind::ValueTooBig, 0..2),
+		("00:60:00", ErrorKind::ValueTooBig, 3..5),
+		("00:00:60", ErrorKind::ValueTooBig, 6..8),
+		("10675199.02:48:05.4775808", ErrorKind::ValueTooBig, 0..25),
+		("-10675199.02:48:05.4775809", ErrorKind::ValueTooBig, 0..26),
+		(
+			"-00:00:01",
+			ErrorKind::IsNegative(-Decimal::from(SECOND)),
+			0..9,
+		),
+		(
+			"-10675199.02:48:05.4775808",
+			ErrorKind::IsNegative(-Decimal::from(TIMESPAN_MAX + TICK)),
+			0..26,
+		),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_dotnet(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	assert_eq!(Duration::from_dotnet_ticks(10_000_000), Duration(SECOND));
+	assert_eq!(Duration(199).as_dotnet_ticks(), 1);
+	assert_eq!(
+		Duration::checked_from_dotnet_ticks(u128::MAX / TICK),
+		Some(Duration(u128::MAX / TICK * TICK))
+	);
+	assert_eq!(
+		Duration::checked_from_dotnet_ticks(u128::MAX / TICK + 1),
+		None
+	);
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let n = n % TIMESPAN_MAX;
+		let ticks = Duration(n - n % TICK);
+		let c = Duration(n).format_dotnet().to_string();
+		let g = Duration(n).format_dotnet_general().to_string();
+		assert_eq!(crate::parse_dotnet(&c), Ok(ticks), "{c:?}");
+		assert_eq!(crate::parse_dotnet(&g), Ok(ticks), "{g:?}");
+	}
+}
+
+#[test]
+fn java() {
+	const JAVA_MAX: u128 = i64::MAX as u128 * SECOND + SECOND - 1;
+
+	let tests = [
+		(0, "PT0S"),
+		(1, "PT0.000000001S"),
+		(500 * MILLISECOND, "PT0.5S"),
+		(MINUTE, "PT1M"),
+		(MINUTE + 1100 * MILLISECOND, "PT1M1.1S"),
+		(HOUR, "PT1H"),
+		(HOUR + SECOND, "PT1H1S"),
+		(DAY, "PT24H"),
+		(
+			8 * HOUR + 6 * MINUTE + 12_345 * MILLISECOND,
+			"PT8H6M12.345S",
+		),
+	];
+	for (n, s) in tests {
+		assert_eq!(Duration(n).format_java().to_string(), s);
+		assert_eq!(crate::parse_java(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	// Mostly from the docs of Java's `Duration.parse`.
+	let tests = [
+		("PT20.345S", 20_345 * MILLISECOND),
+		("PT15M", 15 * MINUTE),
+		("PT10H", 10 * HOUR),
+		("P2D", 2 * DAY),
+		("P2DT3H4M", 2 * DAY + 3 * HOUR + 4 * MINUTE),
+		("-PT-6H+3M", 6 * HOUR - 3 * MINUTE),
+		("P-1DT25H", HOUR),
+		("pt1h", HOUR),
+		("+PT1S", SECOND),
+		("PT1,5S", 1500 * MILLISECOND),
+		("PT1.S", SECOND),
+		("-PT0S", 0),
+		("-PT-1.5S", 1500 * MILLISECOND),
+		("PT9223372036854775807.999999999S", JAVA_MAX),
+	];
+	for (s, n) in tests {
+		assert_eq!(crate::parse_java(s), Ok(Duration(n)), "{s:?}");
+	}
+
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("P", ErrorKind::InvalidDuration, 0..1),
+		("PT", ErrorKind::InvalidDuration, 0..2),
+		("P1DT", ErrorKind::InvalidDuration, 0..4),
+		("1H", ErrorKind::InvalidDuration, 0..1),
+		("PT 1S", ErrorKind::InvalidDuration, 2..3),
+		("PT1", ErrorKind::MissingUnit, 2..3),
+		("PT1X", ErrorKind::InvalidUnit, 3..4),
+		("P1W", ErrorKind::InvalidUnit, 2..3),
+		("PT1M1H", ErrorKind::InvalidDuration, 4..6),
+		("PT1S1S", ErrorKind::InvalidDuration, 4..6),
+		("P1H", ErrorKind::InvalidDuration, 1..3),
+		("PT1D", ErrorKind::InvalidDuration, 2..4),
+		("PT1.5M", ErrorKind::InvalidDuration, 2..6),
+		("PT1.1234567890S", ErrorKind::InvalidDuration, 4..14),
+		("PT9223372036854775808S", ErrorKind::ValueTooBig, 2..22),
+		("PT9223372036854775807H", ErrorKind::ValueTooBig, 2..22),
+		("PT1M9223372036854775807S", ErrorKind::ValueTooBig, 0..24),
+		("-PT-9223372036854775808S", ErrorKind::ValueTooBig, 0..24),
+		(
+			"PT-6H3M",
+			ErrorKind::IsNegative(-Decimal::from(6 * HOUR - 3 * MINUTE)),
+			0..7,
+		),
+		(
+			"PT-0.5S",
+			ErrorKind::IsNegative(-Decimal::from(500 * MILLISECOND)),
+			0..7,
+		),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse_java(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		let s = Duration(n).format_java().to_string();
+		assert_eq!(crate::parse_java(&s), Ok(Duration(n)), "{s:?}");
+	}
+}
+
+#[test]
+fn dialect() {
+	use crate::dialect::*;
+
+	fn check<D: Dialect>(s: &str, formatted: &str) {
+		let d = D::parse(s).unwrap();
+		assert_eq!(d.display_as::<D>().to_string(), formatted, "{s:?}");
+		assert_eq!(D::parse(formatted), Ok(d), "{formatted:?}");
+	}
+
+	check::<dialect::Default>("1.5h", "1h 30m");
+	check::<Exact>("1h 30m 0.5ms", "1h 30m 0.0005s");
+	check::<Go>("1.5h", "1h30m0s");
+	check::<Systemd>("90min", "1h 30min");
+	check::<Postgres>("1 day 2 hours", "1 day 02:00:00");
+	check::<Prometheus>("90m", "1h30m");
+	check::<Humantime>("90m", "1h 30m");
+	check::<Dotnet>("1:2:03:04.5", "1.02:03:04.5000000");
+	check::<DotnetGeneral>("1.02:03:04.5", "1:2:03:04.5");
+	check::<Java>("P1DT1H", "PT25H");
+
+	let d = Duration(3_723_004 * MILLISECOND);
+	assert_eq!(
+		d.display_as::<dialect::Default>().to_string(),
+		d.to_string()
+	);
+	assert_eq!(
+		d.display_as::<Humantime>().to_string(),
+		d.format_humantime().to_string()
+	);
+	assert_eq!(Go::parse("1d"), crate::parse_go("1d"));
+
+	// A dialect from outside the crate.
+	struct Millis;
+
+	impl Dialect for Millis {
+		fn parse(input: &str) -> Result<Duration, Error> {
+			let n = input
+				.parse::<u128>()
+				.map_err(|_| Error::new(ErrorKind::InvalidDuration, 0..input.len()))?;
+			Ok(Duration::from_millis(n))
+		}
+
+		fn format(d: Duration, f: &mut impl fmt::Write) -> fmt::Result {
+			write!(f, "{}", d.as_millis())
+		}
+	}
+
+	check::<Millis>("1500", "1500");
+	assert_eq!(format!("{:>6}", d.display_as::<Millis>()), "3723004");
+}
+
+#[test]
+fn timeout() {
+	let tests = [
+		("inf", Timeout::Infinite),
+		("infinity", Timeout::Infinite),
+		("never", Timeout::Infinite),
+		("none", Timeout::Infinite),
+		("off", Timeout::Infinite),
+		(" Infinity ", Timeout::Infinite),
+		("OFF", Timeout::Infinite),
+		("0", Timeout::Finite(Duration(0))),
+		("30s", Timeout::Finite(Duration(30 * SECOND))),
+		("1h 30m", Timeout::Finite(Duration(90 * MINUTE))),
+	];
+	for (s, t) in tests {
+		assert_eq!(Timeout::parse(s), Ok(t), "{s:?}");
+		assert_eq!(t.to_string().parse::<Timeout>(), Ok(t), "{s:?}");
+	}
+	for s in ["", "forever", "infinite", "-1s", "in f"] {
+		assert!(Timeout::parse(s).is_err(), "{s:?}");
+	}
+	let e = Timeout::parse("5 minuts").unwrap_err();
+	assert_eq!(e, crate::parse("5 minuts").unwrap_err());
+
+	assert_eq!(Timeout::Infinite.to_string(), "infinity");
+	assert_eq!(format!("{:>10}", Timeout::Infinite), "  infinity");
+	assert_eq!(
+		format!("{:#}", Timeout::Finite(Duration(SECOND))),
+		"1 second"
+	);
+
+	let zero = Timeout::Finite(Duration(0));
+	assert_eq!(zero.zero_as_infinite(), Timeout::Infinite);
+	assert_eq!(
+		Timeout::Finite(Duration(1)).zero_as_infinite(),
+		Timeout::Finite(Duration(1))
+	);
+	assert_eq!(Timeout::Infinite.zero_as_infinite(), Timeout::Infinite);
+
+	let mut ts = [
+		Timeout::Infinite,
+		Timeout::Finite(Duration::MAX),
+		zero,
+		Timeout::Finite(Duration(SECOND)),
+	];
+	ts.sort();
+	assert_eq!(
+		ts,
+		[
+			zero,
+			Timeout::Finite(Duration(SECOND)),
+			Timeout::Finite(Duration::MAX),
+			Timeout::Infinite,
+		]
+	);
+	assert!(Timeout::Infinite > Duration::MAX);
+	assert!(Timeout::Finite(Duration(SECOND)) < Duration(2 * SECOND));
+	assert_eq!(Timeout::Finite(Duration(SECOND)), Duration(SECOND));
+
+	assert_eq!(Timeout::Infinite.to_std(), None);
+	assert_eq!(
+		Timeout::Finite(Duration(SECOND)).to_std(),
+		Some(StdDuration::from_secs(1))
+	);
+	assert_eq!(Timeout::Finite(Duration::MAX).to_std(), None);
+	assert_eq!(Option::<StdDuration>::from(zero), Some(StdDuration::ZERO));
+	assert_eq!(Timeout::from(None), Timeout::Infinite);
+	assert_eq!(Timeout::from(StdDuration::from_secs(1)), Duration(SECOND));
+	assert_eq!(Option::<Duration>::from(Timeout::Infinite), None);
+	assert!(Timeout::Infinite.is_infinite() && !zero.is_infinite());
+}
+
+#[test]
+fn bounded() {
+	type Interval = BoundedDuration<SECOND, HOUR>;
+	let out_of_range = ErrorKind::OutOfRange {
+		min: Duration(SECOND),
+		max: Duration(HOUR),
+	};
+
+	let tests = [
+		("1s", SECOND),
+		("5m", 5 * MINUTE),
+		("1h", HOUR),
+		("0.5h 30m", HOUR),
+	];
+	for (s, n) in tests {
+		assert_eq!(
+			s.parse::<Interval>().map(Interval::get),
+			Ok(Duration(n)),
+			"{s:?}"
+		);
+	}
+
+	let tests = [
+		("999ms", out_of_range, 0..5),
+		("1h 1ns", out_of_range, 0..6),
+		("0", out_of_range, 0..1),
+		("5 minuts", ErrorKind::InvalidUnit, 2..8),
+		("", ErrorKind::InvalidDuration, 0..0),
+	];
+	for (s, kind, span) in tests {
+		let e = Interval::parse(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+	assert_eq!(
+		ErrorKind::OutOfRange {
+			min: Duration(SECOND),
+			max: Duration(HOUR),
+		}
+		.to_string(),
+		"the duration must be between 1s and 1h"
+	);
+
+	assert_eq!(Interval::MIN.get(), Duration(SECOND));
+	assert_eq!(Interval::MAX.get(), Duration(HOUR));
+	assert_eq!(Interval::new(Duration(0)), None);
+	assert_eq!(Interval::new(Duration(HOUR + 1)), None);
+	assert_eq!(
+		Interval::new(Duration(MINUTE)).map(Interval::get),
+		Some(Duration(MINUTE))
+	);
+	assert_eq!(Interval::new_saturating(Duration(0)), Interval::MIN);
+	assert_eq!(Interval::new_saturating(Duration::MAX), Interval::MAX);
+	assert_eq!(Interval::new_saturating(Duration(MINUTE)), Duration(MINUTE));
+	assert_eq!(
+		Interval::try_from(Duration(DAY)).map_err(|e| e.kind()),
+		Err(out_of_range)
+	);
+	assert_eq!(Duration::from(Interval::MAX), Duration(HOUR));
+	assert_eq!(Interval::MAX.to_string(), "1h");
+
+	// A single allowed value.
+	type Exactly = BoundedDuration<SECOND, SECOND>;
+	assert_eq!(Exactly::new_saturating(Duration(0)), Duration(SECOND));
+	assert!(Exactly::parse("1000ms").is_ok());
+}
+
+#[test]
+fn whole() {
+	use crate::granularity::*;
+
+	let tests = [
+		("90s", 90, "90s"),
+		("1m 30s", 90, "90s"),
+		("1.5m", 90, "90s"),
+		("1500ms", 1, ""),
+		("0", 0, "0s"),
+		("2h", 7200, "7200s"),
+	];
+	for (s, n, formatted) in tests {
+		let Ok(t) = Seconds::parse(s) else {
+			assert!(formatted.is_empty(), "{s:?}");
+			continue;
+		};
+		assert_eq!(t.count(), n, "{s:?}");
+		assert_eq!(t.to_string(), formatted, "{s:?}");
+		assert_eq!(formatted.parse::<Seconds>(), Ok(t), "{s:?}");
+	}
+	assert_eq!(format!("{:>5}", Seconds::new(90)), "  90s");
+	assert_eq!(format!("{:<6}|", Millis::new(5)), "5ms   |");
+
+	let tests = [
+		("1.5s", ErrorKind::NotWhole(Unit::Second), 0..4),
+		("1s 1ns", ErrorKind::NotWhole(Unit::Second), 0..6),
+		("5 secnds", ErrorKind::InvalidUnit, 2..8),
+	];
+	for (s, kind, span) in tests {
+		let e = Seconds::parse(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+	assert_eq!(
+		ErrorKind::NotWhole(Unit::Millisecond).to_string(),
+		"the duration must be a whole number of milliseconds"
+	);
+
+	assert_eq!(Millis::parse("1.5s").map(Millis::count), Ok(1500));
+	assert_eq!(Millis::new(1500).to_string(), "1500ms");
+	assert!(Millis::parse("1.5ms").is_err());
+	assert_eq!(
+		Whole::<Nanosecond>::parse("1.5us").map(Whole::count),
+		Ok(1500)
+	);
+	assert_eq!(Whole::<Week>::new(2).to_string(), "2w");
+	assert_eq!(
+		Whole::<Year>::parse("2y").map(Whole::get),
+		Ok(Duration(2 * YEAR))
+	);
+	assert_eq!(Whole::<Day>::from_duration(Duration(36 * HOUR)), None);
+	assert_eq!(
+		Whole::<Hour>::from_duration(Duration(DAY)).map(Whole::count),
+		Some(24)
+	);
+	assert_eq!(
+		Whole::<Minute>::try_from(Duration(SECOND)).map_err(|e| e.kind()),
+		Err(ErrorKind::NotWhole(Unit::Minute))
+	);
+	assert_eq!(Duration::from(Seconds::new(3)), Duration(3 * SECOND));
+	assert_eq!(Seconds::new(3), Duration(3 * SECOND));
+	assert!(Seconds::new(59) < Seconds::new(60));
+
+	// Every unit round-trips through its display, up to where `parse` can
+	// read it.
+	for n in (0..200).map(|n| n * 7_919_000_000_123_457_u128 + n) {
+		fn check<G: Granularity>(n: u128) {
+			let w = Whole::<G>::new(n % 1_000_000_000_000);
+			assert_eq!(w.to_string().parse::<Whole<G>>(), Ok(w), "{w}");
+		}
+		check::<Nanosecond>(n);
+		check::<Microsecond>(n);
+		check::<Millisecond>(n);
+		check::<Second>(n);
+		check::<Minute>(n);
+		check::<Hour>(n);
+		check::<Day>(n);
+		check::<Week>(n);
+		check::<Year>(n);
+	}
+}
+
+#[test]
+fn precision() {
+	const ROUND: Parser = Parser::new().with_precision(Precision::RoundHalfEven);
+	const EXACT: Parser = Parser::new().with_precision(Precision::Exact);
+
+	let tests = [
+		// Input, truncated, rounded
+		("0.5ns", 0, 0),
+		("1.5ns", 1, 2),
+		("2.5ns", 2, 2),
+		("2.5000001ns", 2, 3),
+		("1.0000000001s", SECOND, SECOND),
+		("1.0000000006s", SECOND, SECOND + 1),
+		("0.0000015", 1, 2),
+		("0.5ns 0.5ns", 0, 0),
+		("1.5ns 1.5ns", 2, 4),
+	];
+	for (s, truncated, rounded) in tests {
+		assert_eq!(crate::parse(s), Ok(Duration(truncated)), "{s:?}");
+		assert_eq!(ROUND.parse(s), Ok(Duration(rounded)), "{s:?}");
+		let e = EXACT.parse(s).unwrap_err();
+		assert_eq!(e.kind(), ErrorKind::PrecisionLoss, "{s:?}");
+	}
+	assert_eq!(EXACT.parse("1.5us 2.5ms"), Ok(Duration(2_501_500)));
+
+	let e = EXACT.parse("1h 0.25ns").unwrap_err();
+	assert_eq!(e.span(), 3..9);
+
+	assert_eq!(
+		crate::parse_with_report("1h 0.25ns 1.5ns"),
+		Ok((Duration(HOUR + 1), Decimal::new(75, 2)))
+	);
+	assert_eq!(
+		ROUND.parse_with_report("1.75ns"),
+		Ok((Duration(2), Decimal::new(-25, 2)))
+	);
+	assert_eq!(
+		crate::parse_with_report("2h"),
+		Ok((Duration(2 * HOUR), Decimal::ZERO))
+	);
+
+	// Rationals and custom units keep the remainder exact.
+	static UNITS: [CustomUnit; 1] = [CustomUnit::from_ratio("frame", &[], SECOND, 60)];
+	let parser = Parser::with_units(&UNITS)
+		.with_rationals(true)
+		.with_precision(Precision::Exact);
+	assert_eq!(
+		parser.parse("3 frames 1/4s"),
+		Ok(Duration(300 * MILLISECOND))
+	);
+	for s in ["1 frame", "1/3s"] {
+		let e = parser.parse(s).unwrap_err();
+		assert_eq!(e.kind(), ErrorKind::PrecisionLoss, "{s:?}");
+	}
+	assert_eq!(
+		Parser::with_units(&UNITS).parse_with_report("1 frame"),
+		Ok((
+			Duration(16_666_666),
+			Decimal::new(2, 0) / Decimal::new(3, 0)
+		))
+	);
+}
+
+#[test]
+fn number_syntax() {
+	const ALL: Parser = Parser::new()
+		.with_exponents(true)
+		.with_digit_separators(true)
+		.with_rationals(true)
+		.with_vulgar_fractions(true);
+
+	let tests = [
+		("1e3ms", SECOND),
+		("1E+3ms", SECOND),
+		("2.5e-3s", 2500 * MICROSECOND),
+		("1e-28y", 0),
+		("1_000ms", SECOND),
+		("1_000.000_5s", 1000 * SECOND + 500 * MICROSECOND),
+		("1,000,000ns", MILLISECOND),
+		("1,000.5ms", 1000 * MILLISECOND + 500 * MICROSECOND),
+		("1h,30m", HOUR + 30 * MINUTE),
+		("1h,500ms", HOUR + 500 * MILLISECOND),
+		("1/2h", 30 * MINUTE),
+		("1/3s", 333_333_333),
+		("2/3 h 1/3h", HOUR),
+		("1.5/3m", 30 * SECOND),
+		("½h", 30 * MINUTE),
+		("1½h", 90 * MINUTE),
+		("⅓d", 8 * HOUR),
+		("2⅞s", 2 * SECOND + 875 * MILLISECOND),
+		("1e3/3s", 333 * SECOND + 333_333_333),
+		("1/2", 500 * MICROSECOND),
+		("1,000", SECOND),
+	];
+	for (s, expected) in tests {
+		assert_eq!(ALL.parse(s), Ok(Duration(expected)), "{s:?}");
+	}
+
+	// Every extension is off by default and can be turned on on its own.
+	let toggles = [
+		("1e3ms", Parser::new().with_exponents(true)),
+		("1_000ms", Parser::new().with_digit_separators(true)),
+		("1,000ms", Parser::new().with_digit_separators(true)),
+		("1000/1ms", Parser::new().with_rationals(true)),
+	];
+	for (s, parser) in toggles {
+		assert!(crate::parse(s).is_err(), "{s:?}");
+		assert_eq!(parser.parse(s), Ok(Duration(SECOND)), "{s:?}");
+	}
+	assert!(crate::parse("½h").is_err());
+
+	// Inputs `parse` accepts keep their meaning.
+	for s in [
+		"1h 30m",
+		"1.5h",
+		"1h,30m",
+		"3.1415926535897932384626433832 w",
+		"42",
+		"1e3",
+	] {
+		assert_eq!(ALL.parse(s), crate::parse(s), "{s:?}");
+	}
+
+	let errors = [
+		("1h 1/0s", ErrorKind::DivisionByZero, 3..6),
+		("1h -1/2s", ErrorKind::IsNegative(Decimal::new(-5, 1)), 3..7),
+		("-½h", ErrorKind::IsNegative(Decimal::new(-5, 1)), 0..3),
+		("-1½h", ErrorKind::IsNegative(Decimal::new(-15, 1)), 0..4),
+		("-½", ErrorKind::IsNegative(Decimal::new(-5, 1)), 0..3),
+		("-5e-1s", ErrorKind::IsNegative(Decimal::new(-5, 1)), 0..5),
+		("1e29s", ErrorKind::InvalidDuration, 0..4),
+		("1e28y", ErrorKind::ValueTooBig, 0..5),
+		("1,00s", ErrorKind::InvalidUnit, 1..2),
+	];
+	for (s, kind, span) in errors {
+		let e = ALL.parse(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+	}
+}
+
+#[test]
+#[should_panic = "two custom units"]
+fn custom_unit_duplicate() {
+	static UNITS: [CustomUnit; 2] = [
+		CustomUnit::new("tick", &[], 50 * MILLISECOND),
+		CustomUnit::new("tock", &["ticks"], 50 * MILLISECOND),
+	];
+	Parser::with_units(&UNITS);
+}
+
 #[test]
 fn as_conversions() {
 	let d = Duration(MINUTE);
@@ -62,6 +1577,140 @@ fn as_conversions() {
 	}
 }
 
+#[test]
+fn std_parity() {
+	let tests = [
+		(
+			Duration::from_mins(3),
+			Duration::checked_from_mins(3),
+			3 * MINUTE,
+		),
+		(
+			Duration::from_hours(3),
+			Duration::checked_from_hours(3),
+			3 * HOUR,
+		),
+		(
+			Duration::from_days(3),
+			Duration::checked_from_days(3),
+			3 * DAY,
+		),
+		(
+			Duration::from_weeks(3),
+			Duration::checked_from_weeks(3),
+			3 * WEEK,
+		),
+		(
+			Duration::from_secs(3),
+			Duration::checked_from_secs(3),
+			3 * SECOND,
+		),
+		(
+			Duration::from_millis(3),
+			Duration::checked_from_millis(3),
+			3 * MILLISECOND,
+		),
+		(
+			Duration::from_micros(3),
+			Duration::checked_from_micros(3),
+			3 * MICROSECOND,
+		),
+	];
+	for (d, checked, ns) in tests {
+		assert_eq!(d, Duration(ns));
+		assert_eq!(checked, Some(Duration(ns)));
+	}
+
+	assert_eq!(Duration::checked_from_micros(u128::MAX), None);
+	assert_eq!(Duration::checked_from_weeks(u128::MAX / WEEK + 1), None);
+	assert_eq!(
+		Duration::checked_from_weeks(u128::MAX / WEEK),
+		Some(Duration(u128::MAX / WEEK * WEEK))
+	);
+
+	let d = Duration(3 * DAY + 5 * HOUR + 7 * SECOND + 123_456_789);
+	let s = d.to_std();
+	assert_eq!(d.subsec_nanos(), s.subsec_nanos());
+	assert_eq!(d.subsec_micros(), s.subsec_micros());
+	assert_eq!(d.subsec_millis(), s.subsec_millis());
+	assert_eq!(d.as_secs_f64(), s.as_secs_f64());
+	assert_eq!(d.as_secs_f32(), s.as_secs_f32());
+	assert_eq!(d.as_days(), 3);
+	assert_eq!(d.as_hours(), 3 * 24 + 5);
+	assert_eq!(d.as_mins(), (3 * 24 + 5) * 60);
+	assert_eq!(d.as_weeks(), 0);
+
+	assert_eq!(Duration::from_secs_f64(1.5), Duration(1500 * MILLISECOND));
+	assert_eq!(Duration::from_secs_f32(0.25), Duration(250 * MILLISECOND));
+	assert_eq!(Duration::try_from_secs_f64(-1.0), None);
+	assert_eq!(Duration::try_from_secs_f64(f64::NAN), None);
+	assert_eq!(Duration::try_from_secs_f64(f64::INFINITY), None);
+	assert_eq!(Duration::try_from_secs_f64(1e40), None);
+	assert_eq!(Duration(SECOND).mul_f64(2.5), Duration(2500 * MILLISECOND));
+	assert_eq!(Duration(SECOND).div_f64(4.0), Duration(250 * MILLISECOND));
+	assert_eq!(Duration(HOUR).div_duration_f64(Duration(MINUTE)), 60.0);
+
+	// Floats are rounded to the nearest nanosecond like std does.
+	for secs in [
+		0.999e-9,
+		0.5e-9,
+		1.5e-9,
+		2.5e-9,
+		1.000000007,
+		0.3,
+		1e10 + 0.1,
+	] {
+		assert_eq!(
+			Duration::from_secs_f64(secs),
+			StdDuration::from_secs_f64(secs),
+			"{secs}"
+		);
+		assert_eq!(
+			Duration::from_secs_f32(secs as f32),
+			StdDuration::from_secs_f32(secs as f32),
+			"{secs}"
+		);
+	}
+	assert_eq!(Duration::from_secs_f64(0.999e-9), Duration(1));
+	for (ns, rhs) in [
+		(1_000_000_007, 1.0),
+		(1_000_000_007, 3.0),
+		(999_999_999, 0.1),
+	] {
+		let (d, s) = (Duration(ns), StdDuration::from_nanos(ns as u64));
+		assert_eq!(d.mul_f64(rhs), s.mul_f64(rhs), "{ns} * {rhs}");
+		assert_eq!(d.div_f64(rhs), s.div_f64(rhs), "{ns} / {rhs}");
+	}
+	assert_eq!(
+		Duration(1_000_000_007).mul_f64(1.0),
+		Duration(1_000_000_007)
+	);
+	assert_eq!(
+		Duration::try_from_secs_f64(3.4e29),
+		Some(Duration(
+			339_999_999_999_999_981_428_024_541_184_000_000_000
+		))
+	);
+	assert_eq!(Duration::try_from_secs_f64(3.5e29), None);
+
+	let (a, b) = (Duration(5), Duration(3));
+	assert_eq!(a.checked_sub(b), Some(Duration(2)));
+	assert_eq!(b.checked_sub(a), None);
+	assert_eq!(b.saturating_sub(a), Duration::ZERO);
+	assert_eq!(a.abs_diff(b), b.abs_diff(a));
+	assert_eq!(Duration::MAX.checked_add(a), None);
+	assert_eq!(Duration::MAX.saturating_add(a), Duration::MAX);
+	assert_eq!(Duration::MAX.checked_mul(2), None);
+	assert_eq!(Duration::MAX.saturating_mul(2), Duration::MAX);
+	assert_eq!(a.checked_mul(2), Some(Duration(10)));
+	assert_eq!(a.checked_div(0), None);
+	assert_eq!(a.checked_div(2), Some(Duration(2)));
+
+	assert_eq!(Duration::YEAR, Duration(DAY * 365 + DAY / 4));
+	assert_eq!(Duration::WEEK, Duration::DAY * 7_u8);
+	assert!(Duration::ZERO.is_zero());
+}
+
 #[test]
 fn parse_format() {
 	let tests = [
@@ -117,3 +1766,563 @@ fn serde() {
 		assert_eq!(format!("{sb:?}"), ser, "\nserialized form doesn't match");
 	}
 }
+
+#[test]
+fn eval() {
+	let tests = [
+		("2 * 1h + 30m", "2h 30m"),
+		("(1d - 4h) / 4", "5h"),
+		("1h30m * 3 - 20m", "4h 10m"),
+		("1h / 30m * 5s", "10s"),
+		("max(1h, 90m, 2 * 20m)", "1h 30m"),
+		("min(1h, 90m) + .5s", "1h 0.5s"),
+		("1.5 * 1 h", "1h 30m"),
+		("-2 * -1 * 1h", "2h"),
+		("3 * (10s - 5s)", "15s"),
+		("0.5ns + 0.5ns", "1ns"),
+		("1.5ns * 3", "4ns"),
+	];
+
+	for (expr, expected) in tests {
+		let d = crate::eval(expr).unwrap_or_else(|e| panic!("{expr}: {e}"));
+		assert_eq!(expected, d.format_exact().to_string(), "{expr}");
+	}
+
+	let errors = [
+		("1h - 2h", ErrorKind::Underflow, 0..7),
+		("1h * -1", ErrorKind::Underflow, 0..7),
+		("-1h", ErrorKind::Underflow, 0..3),
+		("1h + 2", ErrorKind::InvalidExpression, 0..6),
+		("1h / 0", ErrorKind::DivisionByZero, 0..6),
+		("1h / 0s", ErrorKind::DivisionByZero, 0..7),
+		("(1h", ErrorKind::InvalidExpression, 3..3),
+		("foo(1h)", ErrorKind::InvalidExpression, 0..3),
+		("1h 2h 3", ErrorKind::InvalidExpression, 6..7),
+		("2 * 3", ErrorKind::MissingUnit, 0..5),
+		(
+			"1h * 79228162514264337593543950335",
+			ErrorKind::ValueTooBig,
+			0..34,
+		),
+		("1h + 2 parsecs", ErrorKind::InvalidUnit, 7..14),
+		("max(1h, 2)", ErrorKind::InvalidExpression, 0..9),
+		("1h # 2h", ErrorKind::InvalidExpression, 3..4),
+	];
+
+	for (expr, kind, span) in errors {
+		let e = crate::eval(expr).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{expr}");
+	}
+
+	// Deep nesting is rejected instead of overflowing the stack.
+	let nested = |open: &str, close: &str, n| open.repeat(n) + "1h" + &close.repeat(n);
+	assert_eq!(crate::eval(&nested("(", ")", 128)), Ok(Duration(HOUR)));
+	assert_eq!(crate::eval(&nested("max(", ")", 128)), Ok(Duration(HOUR)));
+	assert_eq!(crate::eval(&nested("+", "", 128)), Ok(Duration(HOUR)));
+	for (open, close, at) in [
+		("(", ")", 128..129),
+		("- ", "", 256..257),
+		("min(", ")", 512..515),
+	] {
+		let e = crate::eval(&nested(open, close, 200_000)).unwrap_err();
+		assert_eq!(
+			(e.kind(), e.span()),
+			(ErrorKind::InvalidExpression, at),
+			"{open}"
+		);
+	}
+}
+
+#[test]
+fn sum() {
+	let v = [Duration::SECOND, Duration::MINUTE, Duration::HOUR];
+	let total = Duration(SECOND + MINUTE + HOUR);
+	assert_eq!(total, v.iter().sum::<Duration>());
+	assert_eq!(total, v.into_iter().sum::<Duration>());
+	assert_eq!(total, v.iter().map(|d| d.to_std()).sum::<Duration>());
+	assert_eq!(Some(total), Duration::checked_sum(v));
+	assert_eq!(
+		Duration(0),
+		core::iter::empty::<Duration>().sum::<Duration>()
+	);
+
+	let v = [Duration(u128::MAX), Duration(1)];
+	assert_eq!(None, Duration::checked_sum(v));
+	assert_eq!(Duration(u128::MAX), Duration::saturating_sum(v));
+}
+
+#[test]
+fn stats() {
+	let mut stats = DurationStats::new();
+	assert_eq!(None, stats.min());
+	assert_eq!(None, stats.p50());
+	assert_eq!("n=0", stats.to_string());
+
+	stats.extend([1, 2, 3].map(Duration::from_millis));
+	assert_eq!(3, stats.count());
+	assert_eq!(Some(Duration::from_millis(2)), stats.mean());
+	assert_eq!(Some(Duration::from_nanos(816_496)), stats.stddev());
+	assert_eq!(
+		"n=3 min=1ms mean=2ms max=3ms stddev=816.49us p50=1.99ms p90=2.94ms p99=2.94ms",
+		stats.to_string(),
+	);
+
+	// Percentiles stay within the histogram's error bound.
+	let stats = (1..=10_000_u128)
+		.map(|n| Duration(n * MICROSECOND * 7))
+		.collect::<DurationStats>();
+	for (p, expected) in [(50.0, 5_000), (90.0, 9_000), (99.0, 9_900), (100.0, 10_000)] {
+		let actual = stats.percentile(p).unwrap().as_nanos() as f64;
+		let expected = (expected * MICROSECOND * 7) as f64;
+		assert!(
+			(actual - expected).abs() / expected < 0.035,
+			"p{p}: {actual} is too far off from {expected}"
+		);
+	}
+	assert_eq!(
+		Some(Duration(10_000 * MICROSECOND * 7)),
+		stats.p99().max(stats.max())
+	);
+}
+
+#[test]
+fn histogram() {
+	const CAP: usize = DurationHistogram::<0>::capacity_for(2, Duration(HOUR));
+	let mut h = DurationHistogram::<CAP>::new(2);
+	assert_eq!(None, h.quantile(0.5));
+
+	for n in 1..=10_000_u128 {
+		h.record(Duration(n * MICROSECOND));
+	}
+	assert_eq!(10_000, h.count());
+	assert_eq!(Some(Duration(MICROSECOND)), h.min());
+	assert_eq!(Some(Duration(10 * MILLISECOND)), h.max());
+
+	for q in [0.01, 0.25, 0.5, 0.9, 0.99, 0.999] {
+		let actual = h.quantile(q).unwrap().as_nanos() as f64;
+		let expected = q * (10 * MILLISECOND) as f64;
+		assert!(
+			(actual - expected).abs() / expected < 0.01,
+			"q{q}: {actual} is too far off from {expected}"
+		);
+	}
+
+	// Every recorded value lies within its bucket.
+	assert_eq!(10_000, h.iter().map(|b| b.count()).sum::<u64>());
+	for b in h.iter() {
+		assert!(b.low() <= b.high());
+	}
+
+	// Values that are too big end up in the last bucket.
+	h.record(Duration(YEAR));
+	assert_eq!(Some(Duration(YEAR)), h.max());
+	assert_eq!(Some(Duration(YEAR)), h.quantile(1.0));
+
+	// Merging histograms with the same precision is exact.
+	let mut a = DurationHistogram::<CAP>::new(2);
+	let mut b = DurationHistogram::<CAP>::new(2);
+	let mut both = DurationHistogram::<CAP>::new(2);
+	for n in 0..1000_u128 {
+		let d = Duration(n * n * 997);
+		if n % 3 == 0 {
+			a.record(d);
+		} else {
+			b.record(d);
+		}
+		both.record(d);
+	}
+	a.merge(&b);
+	assert_eq!(both, a);
+
+	// Otherwise it's approximate.
+	let mut c =
+		DurationHistogram::<{ DurationHistogram::<0>::capacity_for(3, Duration(HOUR)) }>::new(3);
+	c.merge(&both);
+	assert_eq!(both.count(), c.count());
+	assert_eq!(both.max(), c.max());
+
+	let mut small = DurationHistogram::<
+		{ DurationHistogram::<0>::capacity_for(1, Duration(3 * MILLISECOND)) },
+	>::new(1);
+	small.record(Duration(MILLISECOND));
+	small.record(Duration(2 * MILLISECOND));
+	assert_eq!(
+		concat!(
+			"         low         high      count  cumulative\n",
+			"    983.04us       1.01ms          1      50.00%\n",
+			"      1.96ms       2.03ms          1     100.00%\n",
+		),
+		small.to_string(),
+	);
+
+	// The last bucket covers everything that didn't fit.
+	let mut tiny = DurationHistogram::<32>::new(1);
+	tiny.record(Duration(SECOND));
+	assert_eq!(
+		[(31, SECOND, 1)],
+		[tiny
+			.iter()
+			.map(|b| (b.low().0, b.high().0, b.count()))
+			.next()
+			.unwrap()]
+	);
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_histogram() {
+	let mut h = DurationHistogram::<4096>::new(2);
+	let empty = serde_json::to_string(&h).unwrap();
+	assert_eq!(
+		r#"{"significant_digits":2,"min":null,"max":null,"buckets":[]}"#,
+		empty
+	);
+	assert_eq!(h, serde_json::from_str(&empty).unwrap());
+
+	for n in [5, 50, 500, 5000, 5000] {
+		h.record(Duration(n * MILLISECOND));
+	}
+	let ser = serde_json::to_string(&h).unwrap();
+	let de = serde_json::from_str::<DurationHistogram<4096>>(&ser).unwrap();
+	assert_eq!(h, de);
+
+	// The capacity of the receiving end must be large enough.
+	assert!(serde_json::from_str::<DurationHistogram<512>>(&ser).is_err());
+	assert!(serde_json::from_str::<DurationHistogram<4096>>(
+		r#"{"significant_digits":9,"min":null,"max":null,"buckets":[]}"#
+	)
+	.is_err());
+
+	// Not even enough for the first buckets; this used to panic.
+	let empty = r#"{"significant_digits":3,"min":null,"max":null,"buckets":[]}"#;
+	assert!(serde_json::from_str::<DurationHistogram<100>>(empty).is_err());
+	assert!(serde_json::from_str::<DurationHistogram<2048>>(empty).is_ok());
+
+	// The min and max must agree with the buckets; these used to panic later.
+	let bad = [
+		r#"{"significant_digits":1,"min":"2s","max":"1s","buckets":[[3,1]]}"#,
+		r#"{"significant_digits":1,"min":"1ns","max":"3ns","buckets":[[3,1]]}"#,
+		r#"{"significant_digits":1,"min":"3ns","max":"5ns","buckets":[[3,1]]}"#,
+		r#"{"significant_digits":1,"min":"3ns","max":"3ns","buckets":[[3,1],[3,1]]}"#,
+		r#"{"significant_digits":1,"min":"3ns","max":"3ns","buckets":[]}"#,
+	];
+	for s in bad {
+		assert!(
+			serde_json::from_str::<DurationHistogram<64>>(s).is_err(),
+			"{s}"
+		);
+	}
+	let ok = r#"{"significant_digits":1,"min":"3ns","max":"5ns","buckets":[[3,1],[5,2]]}"#;
+	let h = serde_json::from_str::<DurationHistogram<64>>(ok).unwrap();
+	assert_eq!((h.count(), h.quantile(0.5)), (3, Some(Duration(5))));
+
+	// Merging at a different precision keeps the histogram valid.
+	let mut coarse = DurationHistogram::<512>::new(1);
+	coarse.record(Duration(1000));
+	coarse.record(Duration(1900));
+	let mut fine = DurationHistogram::<4096>::new(3);
+	fine.merge(&coarse);
+	let ser = serde_json::to_string(&fine).unwrap();
+	assert_eq!(fine, serde_json::from_str(&ser).unwrap());
+}
+
+#[cfg_attr(feature = "std", test)]
+#[cfg(feature = "std")]
+fn timer() {
+	let clock = ManualClock::new();
+	let mut sw = Stopwatch::start_with(&clock);
+	assert!(!sw.is_paused());
+	assert_eq!(sw.elapsed(), Duration(0));
+
+	clock.advance(Duration(5 * SECOND));
+	assert_eq!(sw.lap(), Duration(5 * SECOND));
+	clock.advance(Duration(SECOND));
+	sw.pause();
+	sw.pause();
+	assert!(sw.is_paused());
+	clock.advance(Duration(HOUR));
+	assert_eq!(sw.elapsed(), Duration(6 * SECOND));
+	sw.resume();
+	sw.resume();
+	clock.advance(Duration(2 * SECOND));
+	assert_eq!(sw.lap(), Duration(3 * SECOND));
+	assert_eq!(sw.lap(), Duration(0));
+	assert_eq!(sw.elapsed(), Duration(8 * SECOND));
+
+	sw.reset();
+	assert_eq!(sw.elapsed(), Duration(0));
+	clock.advance(Duration(SECOND));
+	assert_eq!(sw.lap(), Duration(SECOND));
+
+	let deadline = Deadline::after_with(StdDuration::from_secs(62), &clock);
+	assert_eq!(deadline.remaining(), Duration(62 * SECOND));
+	assert_eq!(deadline.to_string(), "1m 2s remaining");
+	clock.advance(Duration(62 * SECOND - 1));
+	assert!(!deadline.is_expired());
+	assert_eq!(deadline.remaining(), Duration(1));
+	clock.advance(Duration(SECOND));
+	assert!(deadline.is_expired());
+	assert_eq!(deadline.remaining(), Duration(0));
+	assert_eq!(deadline.to_string(), "expired");
+
+	// A clock running backwards doesn't make time pass.
+	let clock = ManualClock::new();
+	let mut sw = Stopwatch::start_with(&clock);
+	clock.set(Duration(5 * SECOND));
+	assert_eq!(sw.lap(), Duration(5 * SECOND));
+	clock.set(Duration(2 * SECOND));
+	assert_eq!(sw.elapsed(), Duration(2 * SECOND));
+	assert_eq!(sw.lap(), Duration(0));
+	clock.set(Duration(3 * SECOND));
+	assert_eq!(sw.lap(), Duration(SECOND));
+
+	// The real clock never runs backwards.
+	let sw = Stopwatch::start();
+	let a = sw.elapsed();
+	assert!(sw.elapsed() >= a);
+	assert!(!Deadline::after(Duration(HOUR)).is_expired());
+	assert!(Deadline::after(Duration(0)).is_expired());
+}
+
+#[test]
+fn suggestion() {
+	let tests = [
+		("5 minuts", Some("minutes")),
+		("3 secnds", Some("seconds")),
+		("3SECNDS", Some("seconds")),
+		("1 huor", Some("hour")),
+		("2 yaers", Some("years")),
+		("2 milisecond", Some("millisecond")),
+		("7 mls", Some("ms")),
+		("1 minuts2s", Some("minutes")),
+		("1 xx", None),
+		("1 parsec", None),
+		("1 fortnight", None),
+	];
+	for (s, expected) in tests {
+		let e = crate::parse(s).unwrap_err();
+		assert_eq!(e.kind(), ErrorKind::InvalidUnit, "{s:?}");
+		assert_eq!(e.suggestion(), expected, "{s:?}");
+	}
+
+	assert_eq!(Error::from(ErrorKind::MissingUnit).suggestion(), None);
+	#[cfg(feature = "alloc")]
+	assert_eq!(
+		crate::parse("5 minuts").unwrap_err().to_string(),
+		"invalid duration unit `minuts`, did you mean `minutes`?"
+	);
+	#[cfg(feature = "alloc")]
+	assert_eq!(
+		crate::parse("1 parsec").unwrap_err().to_string(),
+		"invalid duration unit `parsec`"
+	);
+	#[cfg(not(feature = "alloc"))]
+	assert_eq!(
+		crate::parse("5 minuts").unwrap_err().to_string(),
+		"invalid duration unit, did you mean `minutes`?"
+	);
+}
+
+#[test]
+fn error() {
+	let tests = [
+		("", ErrorKind::InvalidDuration, 0..0),
+		("-5", ErrorKind::IsNegative(Decimal::from(-5)), 0..2),
+		("1h 5", ErrorKind::MissingUnit, 3..4),
+		("1h, -5m", ErrorKind::IsNegative(Decimal::from(-5)), 4..6),
+		("1h 5 parsecs 2s", ErrorKind::InvalidUnit, 5..12),
+		("1h 5\t%", ErrorKind::InvalidUnit, 5..6),
+		("1h abc", ErrorKind::InvalidDuration, 3..6),
+		("1h 3000000000000y", ErrorKind::ValueTooBig, 3..17),
+	];
+	for (s, kind, span) in tests {
+		let e = crate::parse(s).unwrap_err();
+		assert_eq!((e.kind(), e.span()), (kind, span), "{s:?}");
+		assert_eq!(e, crate::parse(s).unwrap_err());
+	}
+
+	let e = crate::parse("1h 5 parsecs").unwrap_err();
+	#[cfg(feature = "alloc")]
+	{
+		assert_eq!(e.input(), Some("1h 5 parsecs"));
+		assert_eq!(e.fragment(), Some("parsecs"));
+	}
+	#[cfg(not(feature = "alloc"))]
+	assert_eq!((e.input(), e.fragment()), (None, None));
+
+	// The message doesn't depend on the input for other kinds.
+	assert_eq!(
+		crate::parse("1h 5").unwrap_err().to_string(),
+		ErrorKind::MissingUnit.to_string()
+	);
+}
+
+#[cfg_attr(feature = "clap", test)]
+#[cfg(feature = "clap")]
+fn clap_suggestion() {
+	let cmd = clap::Command::new("test")
+		.arg(clap::Arg::new("timeout").value_parser(clap::value_parser!(Duration)));
+	let err = cmd.try_get_matches_from(["test", "5 minuts"]).unwrap_err();
+	let msg = err.to_string();
+	assert!(msg.contains("did you mean `minutes`?"), "{msg}");
+	assert!(msg.contains("'5 minutes'"), "{msg}");
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_suggestion() {
+	let err = serde_json::from_str::<Duration>(r#""3 secnds""#).unwrap_err();
+	assert!(err.to_string().contains("did you mean `seconds`?"), "{err}");
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_dialect() {
+	use crate::dialect::{
+		Go,
+		Java,
+		Serde,
+	};
+
+	let d = Duration::from_mins(90);
+	let mut json = alloc::vec::Vec::new();
+	Serde::<Java>::serialize(&d, &mut serde_json::Serializer::new(&mut json)).unwrap();
+	assert_eq!(json, br#""PT1H30M""#);
+
+	let mut de = serde_json::Deserializer::from_str(r#""PT1H30M""#);
+	assert_eq!(Serde::<Java>::deserialize(&mut de).unwrap(), d);
+	let mut de = serde_json::Deserializer::from_str(r#""1h 30m""#);
+	assert!(Serde::<Go>::deserialize(&mut de).is_err());
+	let mut de = serde_json::Deserializer::from_str("5400000");
+	assert!(Serde::<Go>::deserialize(&mut de).is_err());
+}
+
+#[cfg_attr(feature = "clap", test)]
+#[cfg(feature = "clap")]
+fn clap_dialect() {
+	use crate::dialect::{
+		ClapParser,
+		Go,
+	};
+
+	let cmd = clap::Command::new("test")
+		.arg(clap::Arg::new("timeout").value_parser(ClapParser::<Go>::new()));
+	let m = cmd.clone().try_get_matches_from(["test", "1h30m"]).unwrap();
+	assert_eq!(
+		m.get_one::<Duration>("timeout"),
+		Some(&Duration::from_mins(90))
+	);
+	assert!(cmd.try_get_matches_from(["test", "1h 30m"]).is_err());
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_timeout() {
+	let tests = [
+		(Timeout::Infinite, r#""infinity""#),
+		(Timeout::Finite(Duration(90 * MINUTE)), r#""1h 30m""#),
+	];
+	for (t, json) in tests {
+		assert_eq!(serde_json::to_string(&t).unwrap(), json);
+		assert_eq!(serde_json::from_str::<Timeout>(json).unwrap(), t);
+	}
+	assert_eq!(
+		serde_json::from_str::<Timeout>(r#""never""#).unwrap(),
+		Timeout::Infinite
+	);
+	assert!(serde_json::from_str::<Timeout>(r#""forever""#).is_err());
+}
+
+#[cfg_attr(feature = "clap", test)]
+#[cfg(feature = "clap")]
+fn clap_timeout() {
+	use clap::builder::TypedValueParser;
+
+	let cmd = clap::Command::new("test")
+		.arg(clap::Arg::new("timeout").value_parser(clap::value_parser!(Timeout)));
+	let m = cmd.clone().try_get_matches_from(["test", "off"]).unwrap();
+	assert_eq!(m.get_one::<Timeout>("timeout"), Some(&Timeout::Infinite));
+	let m = cmd.clone().try_get_matches_from(["test", "5s"]).unwrap();
+	assert_eq!(
+		m.get_one::<Timeout>("timeout"),
+		Some(&Timeout::Finite(Duration(5 * SECOND)))
+	);
+	let msg = cmd
+		.try_get_matches_from(["test", "5 minuts"])
+		.unwrap_err()
+		.to_string();
+	assert!(msg.contains("'5 minutes'"), "{msg}");
+
+	let cmd = clap::Command::new("test").arg(
+		clap::Arg::new("timeout")
+			.value_parser(clap::value_parser!(Timeout).map(Timeout::zero_as_infinite)),
+	);
+	let m = cmd.try_get_matches_from(["test", "0"]).unwrap();
+	assert_eq!(m.get_one::<Timeout>("timeout"), Some(&Timeout::Infinite));
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_bounded() {
+	type Interval = BoundedDuration<SECOND, HOUR>;
+
+	let i = serde_json::from_str::<Interval>(r#""5m""#).unwrap();
+	assert_eq!(i, Duration(5 * MINUTE));
+	assert_eq!(serde_json::to_string(&i).unwrap(), r#""5m""#);
+	let err = serde_json::from_str::<Interval>(r#""2h""#).unwrap_err();
+	assert!(
+		err.to_string()
+			.contains("the duration must be between 1s and 1h"),
+		"{err}"
+	);
+}
+
+#[cfg_attr(feature = "clap", test)]
+#[cfg(feature = "clap")]
+fn clap_bounded() {
+	type Interval = BoundedDuration<SECOND, HOUR>;
+
+	let cmd = clap::Command::new("test")
+		.arg(clap::Arg::new("interval").value_parser(clap::value_parser!(Interval)));
+	let m = cmd.clone().try_get_matches_from(["test", "5m"]).unwrap();
+	assert_eq!(
+		m.get_one::<Interval>("interval").copied(),
+		Interval::new(Duration(5 * MINUTE))
+	);
+	let msg = cmd
+		.try_get_matches_from(["test", "2h"])
+		.unwrap_err()
+		.to_string();
+	assert!(
+		msg.contains("the duration must be between 1s and 1h"),
+		"{msg}"
+	);
+}
+
+#[cfg_attr(feature = "serde", test)]
+#[cfg(feature = "serde")]
+fn serde_whole() {
+	let t = serde_json::from_str::<Seconds>(r#""1m 30s""#).unwrap();
+	assert_eq!(t.count(), 90);
+	assert_eq!(serde_json::to_string(&t).unwrap(), r#""90s""#);
+	let err = serde_json::from_str::<Seconds>(r#""1.5s""#).unwrap_err();
+	assert!(
+		err.to_string()
+			.contains("the duration must be a whole number of seconds"),
+		"{err}"
+	);
+}
+
+#[cfg_attr(feature = "clap", test)]
+#[cfg(feature = "clap")]
+fn clap_whole() {
+	let cmd = clap::Command::new("test")
+		.arg(clap::Arg::new("ttl").value_parser(clap::value_parser!(Seconds)));
+	let m = cmd.clone().try_get_matches_from(["test", "2m"]).unwrap();
+	assert_eq!(m.get_one::<Seconds>("ttl"), Some(&Seconds::new(120)));
+	let msg = cmd
+		.try_get_matches_from(["test", "2.5s"])
+		.unwrap_err()
+		.to_string();
+	assert!(msg.contains("whole number of seconds"), "{msg}");
+}
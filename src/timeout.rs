@@ -0,0 +1,153 @@
+use core::cmp::Ordering;
+
+use crate::*;
+
+/// The words [Timeout::parse] accepts for [Timeout::Infinite].
+const INFINITE: [&str; 5] = ["inf", "infinity", "never", "none", "off"];
+
+/// A timeout that can be infinite, such as in a configuration file where
+/// "no timeout" is an option.
+///
+/// [Timeout::Infinite] is greater than every finite timeout, so the smaller
+/// of two timeouts is the stricter one.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{
+///     Duration,
+///     Timeout,
+/// };
+///
+/// let t = "30s".parse::<Timeout>().unwrap();
+/// assert_eq!(t, Timeout::Finite(Duration::from_secs(30)));
+/// assert_eq!("never".parse::<Timeout>(), Ok(Timeout::Infinite));
+/// assert!(t < Timeout::Infinite);
+///
+/// // Some programs treat zero as infinite; that's opt-in.
+/// let t = "0s".parse::<Timeout>().unwrap();
+/// assert_eq!(t.zero_as_infinite(), Timeout::Infinite);
+///
+/// assert_eq!(Timeout::Infinite.to_string(), "infinity");
+/// assert_eq!(Timeout::Infinite.to_std(), None);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Timeout {
+	/// A finite timeout.
+	Finite(Duration),
+	/// No timeout.
+	Infinite,
+}
+
+impl Timeout {
+	/// Parses a timeout.
+	///
+	/// The words `inf`, `infinity`, `never`, `none` and `off`, in any case and
+	/// with optional whitespace around them, are [Timeout::Infinite]; anything
+	/// else is parsed with [parse].
+	/// A zero duration is finite; see [Timeout::zero_as_infinite] for treating
+	/// it as infinite.
+	///
+	/// #### Errors
+	/// Returns an error if `input` isn't one of the words above or a valid
+	/// duration.
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let s = input.trim();
+		if INFINITE.iter().any(|w| w.eq_ignore_ascii_case(s)) {
+			return Ok(Self::Infinite);
+		}
+		parse(input).map(Self::Finite)
+	}
+
+	/// Returns [Timeout::Infinite] if `self` is a zero duration, otherwise
+	/// `self`, for configurations where `0` means no timeout.
+	///
+	/// With clap, this is `value_parser!(Timeout).map(Timeout::zero_as_infinite)`.
+	pub const fn zero_as_infinite(self) -> Self {
+		match self {
+			Self::Finite(Duration(0)) => Self::Infinite,
+			t => t,
+		}
+	}
+
+	/// Returns `true` if this is [Timeout::Infinite].
+	pub const fn is_infinite(self) -> bool {
+		matches!(self, Self::Infinite)
+	}
+
+	/// Returns the duration of a finite timeout, or [None] if it's infinite.
+	pub const fn to_duration(self) -> Option<Duration> {
+		match self {
+			Self::Finite(d) => Some(d),
+			Self::Infinite => None,
+		}
+	}
+
+	/// Converts to an [StdDuration], or [None] if the timeout is infinite.
+	///
+	/// A finite timeout too big for an [StdDuration], which is more than 500
+	/// billion years, is also [None].
+	pub fn to_std(self) -> Option<StdDuration> {
+		self.to_duration().and_then(Duration::try_to_std)
+	}
+}
+
+impl FromStr for Timeout {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse(s)
+	}
+}
+
+/// Writes a finite timeout like [Duration] and an infinite one as `infinity`.
+impl Display for Timeout {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Finite(d) => Display::fmt(d, f),
+			Self::Infinite => f.pad("infinity"),
+		}
+	}
+}
+
+impl From<Duration> for Timeout {
+	fn from(d: Duration) -> Self {
+		Self::Finite(d)
+	}
+}
+
+impl From<StdDuration> for Timeout {
+	fn from(d: StdDuration) -> Self {
+		Self::Finite(d.into())
+	}
+}
+
+/// [None] is [Timeout::Infinite].
+impl From<Option<Duration>> for Timeout {
+	fn from(d: Option<Duration>) -> Self {
+		d.map_or(Self::Infinite, Self::Finite)
+	}
+}
+
+impl From<Timeout> for Option<Duration> {
+	fn from(t: Timeout) -> Self {
+		t.to_duration()
+	}
+}
+
+impl From<Timeout> for Option<StdDuration> {
+	fn from(t: Timeout) -> Self {
+		t.to_std()
+	}
+}
+
+impl PartialEq<Duration> for Timeout {
+	fn eq(&self, rhs: &Duration) -> bool {
+		*self == Self::Finite(*rhs)
+	}
+}
+
+impl PartialOrd<Duration> for Timeout {
+	fn partial_cmp(&self, rhs: &Duration) -> Option<Ordering> {
+		Some(self.cmp(&Self::Finite(*rhs)))
+	}
+}
@@ -0,0 +1,254 @@
+use std::{
+	cell::Cell,
+	time::Instant,
+};
+
+use crate::*;
+
+/// A source of monotonic time for [Stopwatch] and [Deadline].
+///
+/// [Clock::now] returns the time elapsed since an arbitrary, fixed point.
+/// The default clock is [MonotonicClock]; tests can use [ManualClock] or
+/// their own implementation to control time.
+pub trait Clock {
+	/// Returns the time elapsed since this clock's starting point.
+	fn now(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+	fn now(&self) -> Duration {
+		(**self).now()
+	}
+}
+
+/// A [Clock] backed by [std::time::Instant].
+#[derive(Copy, Clone, Debug)]
+pub struct MonotonicClock {
+	origin: Instant,
+}
+
+impl MonotonicClock {
+	/// Creates a clock whose starting point is the current instant.
+	pub fn new() -> Self {
+		Self {
+			origin: Instant::now(),
+		}
+	}
+}
+
+impl Default for MonotonicClock {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Clock for MonotonicClock {
+	fn now(&self) -> Duration {
+		Duration::from_std(self.origin.elapsed())
+	}
+}
+
+/// A [Clock] that only moves when told to, useful for tests.
+///
+/// Share it between several timers by reference, since `&C` implements
+/// [Clock] too.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+	now: Cell<Duration>,
+}
+
+impl ManualClock {
+	/// Creates a clock at time zero.
+	pub const fn new() -> Self {
+		Self {
+			now: Cell::new(Duration(0)),
+		}
+	}
+
+	/// Moves the clock forward by `d`.
+	pub fn advance(&self, d: impl Into<Duration>) {
+		self.now.set(self.now.get().saturating_add(d.into()));
+	}
+
+	/// Sets the current time of the clock.
+	///
+	/// The time can be moved backwards; the timers treat time running
+	/// backwards as no time passing.
+	pub fn set(&self, now: impl Into<Duration>) {
+		self.now.set(now.into());
+	}
+}
+
+impl Clock for ManualClock {
+	fn now(&self) -> Duration {
+		self.now.get()
+	}
+}
+
+/// Measures elapsed time, with support for pausing and laps.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, ManualClock, Stopwatch};
+///
+/// let clock = ManualClock::new();
+/// let mut sw = Stopwatch::start_with(&clock);
+///
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(sw.lap(), Duration::from_secs(3));
+///
+/// clock.advance(Duration::from_secs(2));
+/// sw.pause();
+/// clock.advance(Duration::from_secs(60));
+/// sw.resume();
+/// clock.advance(Duration::from_secs(1));
+///
+/// assert_eq!(sw.lap(), Duration::from_secs(3));
+/// assert_eq!(sw.elapsed(), Duration::from_secs(6));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Stopwatch<C: Clock = MonotonicClock> {
+	clock: C,
+	/// Time accumulated before the current run.
+	elapsed: Duration,
+	/// The clock time the current run started at, if running.
+	started: Option<Duration>,
+	/// The value of [Stopwatch::elapsed] at the last lap.
+	lap: Duration,
+}
+
+impl Stopwatch {
+	/// Creates a running stopwatch using the [MonotonicClock].
+	pub fn start() -> Self {
+		Self::start_with(MonotonicClock::new())
+	}
+}
+
+impl<C: Clock> Stopwatch<C> {
+	/// Creates a running stopwatch using the given clock.
+	pub fn start_with(clock: C) -> Self {
+		let started = Some(clock.now());
+		Self {
+			clock,
+			elapsed: Duration(0),
+			started,
+			lap: Duration(0),
+		}
+	}
+
+	/// Returns the total running time, excluding the time spent paused.
+	pub fn elapsed(&self) -> Duration {
+		match self.started {
+			Some(t) => self
+				.elapsed
+				.saturating_add(Duration(self.clock.now().0.saturating_sub(t.0))),
+			None => self.elapsed,
+		}
+	}
+
+	/// Returns the running time since the previous lap (or the start) and
+	/// starts a new lap.
+	pub fn lap(&mut self) -> Duration {
+		let elapsed = self.elapsed();
+		let lap = Duration(elapsed.0.saturating_sub(self.lap.0));
+		self.lap = elapsed;
+		lap
+	}
+
+	/// Pauses the stopwatch. Does nothing if it's already paused.
+	pub fn pause(&mut self) {
+		if self.started.is_some() {
+			self.elapsed = self.elapsed();
+			self.started = None;
+		}
+	}
+
+	/// Resumes the stopwatch. Does nothing if it's already running.
+	pub fn resume(&mut self) {
+		if self.started.is_none() {
+			self.started = Some(self.clock.now());
+		}
+	}
+
+	/// Returns true if the stopwatch is paused.
+	pub const fn is_paused(&self) -> bool {
+		self.started.is_none()
+	}
+
+	/// Resets the elapsed time and the laps to zero, keeping the stopwatch
+	/// running or paused.
+	pub fn reset(&mut self) {
+		self.elapsed = Duration(0);
+		self.lap = Duration(0);
+		if self.started.is_some() {
+			self.started = Some(self.clock.now());
+		}
+	}
+}
+
+/// A point in time after which something is considered expired, such as a
+/// timeout.
+///
+/// Its [Display] implementation shows the time remaining, e.g.
+/// `3m 2s remaining`, or `expired`.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Deadline, Duration, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let deadline = Deadline::after_with(Duration::from_secs(185), &clock);
+/// assert_eq!(deadline.to_string(), "3m 5s remaining");
+///
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(deadline.to_string(), "3m 2s remaining");
+/// assert!(!deadline.is_expired());
+///
+/// clock.advance(Duration::from_secs(600));
+/// assert_eq!(deadline.remaining(), Duration::from_nanos(0));
+/// assert!(deadline.is_expired());
+/// assert_eq!(deadline.to_string(), "expired");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Deadline<C: Clock = MonotonicClock> {
+	clock: C,
+	at: Duration,
+}
+
+impl Deadline {
+	/// Creates a deadline `d` from now, using the [MonotonicClock].
+	pub fn after(d: impl Into<Duration>) -> Self {
+		Self::after_with(d, MonotonicClock::new())
+	}
+}
+
+impl<C: Clock> Deadline<C> {
+	/// Creates a deadline `d` from now, using the given clock.
+	pub fn after_with(d: impl Into<Duration>, clock: C) -> Self {
+		let at = clock.now().saturating_add(d.into());
+		Self { clock, at }
+	}
+
+	/// Returns the time left until the deadline, or zero if it has passed.
+	pub fn remaining(&self) -> Duration {
+		Duration(self.at.0.saturating_sub(self.clock.now().0))
+	}
+
+	/// Returns true if the deadline has passed.
+	pub fn is_expired(&self) -> bool {
+		self.remaining().is_zero()
+	}
+}
+
+impl<C: Clock> Display for Deadline<C> {
+	/// The formatting flags are forwarded to the remaining [Duration].
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let remaining = self.remaining();
+		if remaining.is_zero() {
+			f.write_str("expired")
+		} else {
+			Display::fmt(&remaining, f)?;
+			f.write_str(" remaining")
+		}
+	}
+}
@@ -0,0 +1,248 @@
+use crate::*;
+
+/// A unit of time recognized by the parser.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{Duration, Unit};
+///
+/// let unit = "Hours".parse::<Unit>().unwrap();
+/// assert_eq!(unit, Unit::Hour);
+/// assert_eq!(unit.short_name(), "h");
+/// assert_eq!(unit.long_name(), "hour");
+/// assert!(unit.aliases().contains(&"hrs"));
+/// assert_eq!(Duration::from_unit(1.into(), unit), Duration::from_secs(3600));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Unit {
+	/// A nanosecond.
+	Nanosecond,
+	/// A microsecond of 1000 nanoseconds.
+	Microsecond,
+	/// A millisecond of 1000 microseconds.
+	Millisecond,
+	/// A second of 1000 milliseconds.
+	Second,
+	/// A minute of 60 seconds.
+	Minute,
+	/// An hour of 60 minutes.
+	Hour,
+	/// A day of 24 hours.
+	Day,
+	/// A week of 7 days.
+	Week,
+	/// A Julian year of 365.25 days.
+	Year,
+}
+
+impl Unit {
+	/// All the units, from the shortest to the longest.
+	pub const ALL: [Self; 9] = [
+		Self::Nanosecond,
+		Self::Microsecond,
+		Self::Millisecond,
+		Self::Second,
+		Self::Minute,
+		Self::Hour,
+		Self::Day,
+		Self::Week,
+		Self::Year,
+	];
+
+	/// Returns the length of this unit in nanoseconds.
+	pub const fn nanos(self) -> u128 {
+		match self {
+			Self::Nanosecond => 1,
+			Self::Microsecond => MICROSECOND,
+			Self::Millisecond => MILLISECOND,
+			Self::Second => SECOND,
+			Self::Minute => MINUTE,
+			Self::Hour => HOUR,
+			Self::Day => DAY,
+			Self::Week => WEEK,
+			Self::Year => YEAR,
+		}
+	}
+
+	/// Returns the abbreviated name of this unit, as used by the [Display]
+	/// implementation of [Duration], e.g. `ms`.
+	pub const fn short_name(self) -> &'static str {
+		match self {
+			Self::Nanosecond => "ns",
+			Self::Microsecond => "us",
+			Self::Millisecond => "ms",
+			Self::Second => "s",
+			Self::Minute => "m",
+			Self::Hour => "h",
+			Self::Day => "d",
+			Self::Week => "w",
+			Self::Year => "yr",
+		}
+	}
+
+	/// Returns the singular full name of this unit, e.g. `millisecond`.
+	pub const fn long_name(self) -> &'static str {
+		match self {
+			Self::Nanosecond => "nanosecond",
+			Self::Microsecond => "microsecond",
+			Self::Millisecond => "millisecond",
+			Self::Second => "second",
+			Self::Minute => "minute",
+			Self::Hour => "hour",
+			Self::Day => "day",
+			Self::Week => "week",
+			Self::Year => "year",
+		}
+	}
+
+	/// Returns every name the parser accepts for this unit.
+	///
+	/// Names are matched case-insensitively.
+	pub const fn aliases(self) -> &'static [&'static str] {
+		match self {
+			Self::Nanosecond => &["nanoseconds", "nanosecond", "nanos", "ns"],
+			Self::Microsecond => &["microseconds", "microsecond", "micros", "us", "µs"],
+			Self::Millisecond => &["milliseconds", "millisecond", "millis", "ms"],
+			Self::Second => &["seconds", "second", "secs", "sec", "s"],
+			Self::Minute => &["minutes", "minute", "mins", "min", "m"],
+			Self::Hour => &["hours", "hour", "hrs", "hr", "h"],
+			Self::Day => &["days", "day", "d"],
+			Self::Week => &["weeks", "week", "w"],
+			Self::Year => &["years", "year", "yrs", "yr", "y"],
+		}
+	}
+}
+
+/// Returns true if `s` starts with `prefix`, ignoring ASCII case.
+pub(crate) const fn starts_with_ignore_case(s: &[u8], prefix: &[u8]) -> bool {
+	if s.len() < prefix.len() {
+		return false;
+	}
+	let mut i = 0;
+	while i < prefix.len() {
+		if !s[i].eq_ignore_ascii_case(&prefix[i]) {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+/// Finds the unit with the longest alias that `s` starts with.
+///
+/// Returns the unit and the length of the alias in bytes.
+pub(crate) const fn match_unit(s: &[u8]) -> Option<(Unit, usize)> {
+	let mut best = None;
+	let mut best_len = 0;
+
+	let mut i = 0;
+	while i < Unit::ALL.len() {
+		let aliases = Unit::ALL[i].aliases();
+		let mut j = 0;
+		while j < aliases.len() {
+			let alias = aliases[j].as_bytes();
+			if alias.len() > best_len && starts_with_ignore_case(s, alias) {
+				best = Some(Unit::ALL[i]);
+				best_len = alias.len();
+			}
+			j += 1;
+		}
+		i += 1;
+	}
+
+	match best {
+		Some(unit) => Some((unit, best_len)),
+		None => None,
+	}
+}
+
+impl Display for Unit {
+	/// Prints the [short name](Self::short_name), or the
+	/// [long name](Self::long_name) with the `#` flag.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.write_str(self.long_name())
+		} else {
+			f.write_str(self.short_name())
+		}
+	}
+}
+
+impl FromStr for Unit {
+	type Err = Error;
+
+	/// Parses any of the [aliases](Self::aliases) of a unit, ignoring case and
+	/// surrounding whitespace.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let trimmed = s.trim();
+		let start = s.len() - s.trim_start().len();
+		let span = start..start + trimmed.len();
+		match match_unit(trimmed.as_bytes()) {
+			_ if trimmed.is_empty() => Err(Error::new(ErrorKind::MissingUnit, span)),
+			Some((unit, len)) if len == trimmed.len() => Ok(unit),
+			_ => Err(Error::new(ErrorKind::InvalidUnit, span)
+				.with_suggestion(suggest(trimmed, &[]))
+				.with_input(s)),
+		}
+	}
+}
+
+/// Returns the optimal string alignment distance between `a` and `b`, ignoring
+/// ASCII case, or [None] if `a` is too long.
+fn distance(a: &str, b: &str) -> Option<usize> {
+	const MAX: usize = 32;
+
+	let mut chars = ['\0'; MAX];
+	let mut n = 0;
+	for c in a.chars() {
+		*chars.get_mut(n)? = c.to_ascii_lowercase();
+		n += 1;
+	}
+	let a = &chars[..n];
+
+	// Three rows of the distance matrix: `i - 2`, `i - 1` and `i`.
+	let mut prev2 = [0; MAX + 1];
+	let mut prev: [usize; MAX + 1] = core::array::from_fn(|j| j);
+	let mut cur = [0; MAX + 1];
+
+	let mut last = '\0';
+	for (i, c) in b.chars().map(|c| c.to_ascii_lowercase()).enumerate() {
+		cur[0] = i + 1;
+		for j in 1..=a.len() {
+			let cost = (a[j - 1] != c) as usize;
+			cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+			if i > 0 && j > 1 && a[j - 1] == last && a[j - 2] == c {
+				cur[j] = cur[j].min(prev2[j - 2] + 1);
+			}
+		}
+		last = c;
+		prev2 = prev;
+		prev = cur;
+	}
+
+	Some(prev[a.len()])
+}
+
+/// Returns the built-in or custom unit spelling closest to `unit`, if any is
+/// close enough to be a likely typo.
+pub(crate) fn suggest(unit: &str, custom: &[CustomUnit]) -> Option<&'static str> {
+	let max = match unit.chars().count() {
+		0..=2 => return None,
+		3..=5 => 1,
+		_ => 2,
+	};
+
+	let custom = custom
+		.iter()
+		.flat_map(|u| core::iter::once(u.name()).chain(u.aliases().iter().copied()));
+
+	Unit::ALL
+		.iter()
+		.flat_map(|u| u.aliases().iter().copied())
+		.chain(custom)
+		.filter_map(|alias| Some((distance(unit, alias)?, alias)))
+		.filter(|&(d, _)| d <= max)
+		// `min_by_key` returns the first minimum, the longer spelling.
+		.min_by_key(|&(d, _)| d)
+		.map(|(_, alias)| alias)
+}
@@ -0,0 +1,132 @@
+use core::{
+	fmt::Write as _,
+	marker::PhantomData,
+};
+
+use crate::{
+	granularity::{
+		self,
+		Granularity,
+	},
+	histogram::Buf,
+	*,
+};
+
+/// A duration of whole seconds.
+pub type Seconds = Whole<granularity::Second>;
+/// A duration of whole milliseconds.
+pub type Millis = Whole<granularity::Millisecond>;
+
+/// A [Duration] that is a whole number of a [Granularity], such as whole
+/// seconds.
+///
+/// Durations that aren't whole, such as `1.5s` for whole seconds, are rejected
+/// when parsing and, with the `serde` and `clap` features, when deserializing
+/// and parsing command line arguments, instead of being truncated later.
+/// [Seconds] and [Millis] are the common ones.
+///
+/// It's displayed as a number in the unit, such as `90s`, which
+/// [parse] reads back.
+///
+/// #### Examples
+/// ```rust
+/// use dur::{
+///     granularity::Minute,
+///     Duration,
+///     ErrorKind,
+///     Seconds,
+///     Unit,
+///     Whole,
+/// };
+///
+/// let ttl = "1m 30s".parse::<Seconds>().unwrap();
+/// assert_eq!(ttl.count(), 90);
+/// assert_eq!(ttl.to_string(), "90s");
+///
+/// let e = "1.5s".parse::<Seconds>().unwrap_err();
+/// assert_eq!(e.kind(), ErrorKind::NotWhole(Unit::Second));
+///
+/// assert_eq!(Whole::<Minute>::new(3).get(), Duration::from_mins(3));
+/// assert_eq!(Whole::<Minute>::from_duration(Duration::from_secs(90)), None);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Whole<G>(Duration, PhantomData<G>);
+
+impl<G: Granularity> Whole<G> {
+	/// Creates a duration of `count` units.
+	///
+	/// #### Overflow Behavior
+	/// If the value in nanoseconds overflows a [u128], the behavior is the same
+	/// as with [u128] overflow with multiplication.
+	pub const fn new(count: u128) -> Self {
+		Self(Duration(count * G::UNIT.nanos()), PhantomData)
+	}
+
+	/// Returns `d` if it's a whole number of units, otherwise [None].
+	pub const fn from_duration(d: Duration) -> Option<Self> {
+		if d.0.is_multiple_of(G::UNIT.nanos()) {
+			Some(Self(d, PhantomData))
+		} else {
+			None
+		}
+	}
+
+	/// Returns the number of units.
+	pub const fn count(self) -> u128 {
+		self.0 .0 / G::UNIT.nanos()
+	}
+
+	/// Returns the duration.
+	pub const fn get(self) -> Duration {
+		self.0
+	}
+
+	/// Parses a duration with [parse] and checks that it's a whole number of
+	/// units.
+	///
+	/// #### Errors
+	/// Returns an error if [parse] does or, if the duration isn't whole, an
+	/// [ErrorKind::NotWhole] spanning all of `input`.
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		Self::try_from(parse(input)?)
+			.map_err(|e| Error::new(e.kind(), 0..input.len()).with_input(input))
+	}
+}
+
+impl<G: Granularity> FromStr for Whole<G> {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse(s)
+	}
+}
+
+/// Writes the number of units with the abbreviated unit, such as `90s`.
+impl<G: Granularity> Display for Whole<G> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut buf = Buf::new();
+		write!(buf, "{}{}", self.count(), G::UNIT.short_name())?;
+		f.pad(buf.as_str())
+	}
+}
+
+/// Fails with an [ErrorKind::NotWhole] if the duration isn't whole.
+impl<G: Granularity> TryFrom<Duration> for Whole<G> {
+	type Error = Error;
+
+	fn try_from(d: Duration) -> Result<Self, Self::Error> {
+		Self::from_duration(d).ok_or(Error::from(ErrorKind::NotWhole(G::UNIT)))
+	}
+}
+
+impl<G> From<Whole<G>> for Duration {
+	fn from(d: Whole<G>) -> Self {
+		d.0
+	}
+}
+
+impl<G> PartialEq<Duration> for Whole<G> {
+	fn eq(&self, rhs: &Duration) -> bool {
+		self.0 == *rhs
+	}
+}
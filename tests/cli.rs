@@ -0,0 +1,145 @@
+use std::{
+	io::Write,
+	process::{
+		Command,
+		Output,
+		Stdio,
+	},
+};
+
+fn dur(args: &[&str], stdin: &str) -> Output {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_dur"))
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(stdin.as_bytes())
+		.unwrap();
+	child.wait_with_output().unwrap()
+}
+
+/// Runs `dur` and returns its stdout, asserting that it succeeded.
+fn ok(args: &[&str], stdin: &str) -> String {
+	let out = dur(args, stdin);
+	let stderr = String::from_utf8(out.stderr).unwrap();
+	assert!(out.status.success(), "{args:?}: {stderr}");
+	String::from_utf8(out.stdout).unwrap()
+}
+
+/// Runs `dur` and returns its stderr, asserting that it failed with exit code
+/// 1 and printed nothing to stdout.
+fn fail(args: &[&str], stdin: &str) -> String {
+	let out = dur(args, stdin);
+	assert_eq!(out.status.code(), Some(1), "{args:?}");
+	assert_eq!(out.stdout, b"", "{args:?}");
+	String::from_utf8(out.stderr).unwrap()
+}
+
+#[test]
+fn fmt() {
+	let tests: &[(&[&str], &str)] = &[
+		(&["fmt", "90000s"], "1d 1h\n"),
+		(&["fmt", "1h", "30m"], "1h 30m\n"),
+		(&["fmt", "1.2345s"], "1.23s\n"),
+		(&["fmt", "-p", "3", "1.2345s"], "1.234s\n"),
+		(&["fmt", "-s", "exact", "1h 30m 0.5s"], "1h 30m 0.5s\n"),
+		(&["fmt", "--style", "long", "1.5h"], "1 hour 30 minutes\n"),
+		(&["fmt", "-s", "iso", "1h", "30m"], "PT1H30M\n"),
+	];
+
+	for (args, expected) in tests {
+		assert_eq!(*expected, ok(args, ""), "{args:?}");
+	}
+}
+
+#[test]
+fn to() {
+	let tests: &[(&[&str], &str)] = &[
+		(&["to", "min", "1.5h"], "90\n"),
+		(&["to", "h", "90m"], "1.5\n"),
+		(&["to", "seconds", "1m", "1ms"], "60.001\n"),
+	];
+
+	for (args, expected) in tests {
+		assert_eq!(*expected, ok(args, ""), "{args:?}");
+	}
+}
+
+#[test]
+fn sum() {
+	assert_eq!("1h 30m\n", ok(&["sum"], "1h\n\n  30m  \n"));
+	assert_eq!("0ns\n", ok(&["sum"], ""));
+}
+
+#[test]
+fn calc() {
+	let tests: &[(&[&str], &str)] = &[
+		(&["calc", "1h30m * 3 - 20m"], "4h 10m\n"),
+		(&["calc", "(1d", "-", "4h)", "/", "3"], "6h 40m\n"),
+		(&["calc", "-s", "iso", "max(1h, 90m)"], "PT1H30M\n"),
+	];
+
+	for (args, expected) in tests {
+		assert_eq!(*expected, ok(args, ""), "{args:?}");
+	}
+}
+
+#[test]
+fn json() {
+	assert_eq!(
+		r#"{"nanos":5400000000000,"pretty":"1h 30m","exact":"1h 30m","long":"1 hour 30 minutes","iso":"PT1H30M"}
+"#,
+		ok(&["--json", "fmt", "1.5h"], ""),
+	);
+	assert_eq!(
+		r#"{"unit":"minute","value":"90"}
+"#,
+		ok(&["to", "--json", "min", "1.5h"], ""),
+	);
+	assert_eq!(
+		r#"{"count":2,"nanos":5400000000000,"pretty":"1h 30m","exact":"1h 30m","long":"1 hour 30 minutes","iso":"PT1H30M"}
+"#,
+		ok(&["sum", "--json"], "1h\n30m\n"),
+	);
+}
+
+#[test]
+fn errors() {
+	assert_eq!(
+		"error: invalid duration unit `foo`\n  2 foo\n    ^\n",
+		fail(&["fmt", "2", "foo"], ""),
+	);
+	assert_eq!(
+		"error: invalid duration unit `minuts`, did you mean `minutes`?\n  1µs 5 minuts\n        ^\n",
+		fail(&["fmt", "1µs 5 minuts"], ""),
+	);
+	assert_eq!(
+		"error: line 2: invalid duration\n  x\n  ^\n",
+		fail(&["sum"], "1h\nx\n2h\n"),
+	);
+	assert_eq!(
+		r#"{"error":"invalid duration unit `x`","input":"1h 5x","position":4}
+"#,
+		fail(&["--json", "fmt", "1h 5x"], ""),
+	);
+	assert_eq!(
+		r#"{"error":"invalid duration","line":1,"input":"x","position":0}
+"#,
+		fail(&["--json", "sum"], "x\n"),
+	);
+
+	assert_eq!(
+		"error: the resulting duration is negative\n  1h - 2h\n  ^\n",
+		fail(&["calc", "1h - 2h"], ""),
+	);
+
+	// Usage errors are reported by clap with its own exit code.
+	assert_eq!(dur(&["fmt"], "").status.code(), Some(2));
+	assert_eq!(dur(&["to", "foo", "1h"], "").status.code(), Some(2));
+}